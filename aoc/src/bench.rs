@@ -0,0 +1,101 @@
+use crate::{Context, DaySelection};
+
+use clap::Args;
+use common::anyhow::{anyhow, bail};
+use common::{
+    benchmark, format_duration, registered_days, solver, workspace_root, BenchHistory, BenchRun,
+    Day, Result, Stats, Year,
+};
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Year of the event, e.g. 2015
+    year: u32,
+    /// Day (1-25) or `all`
+    day: DaySelection,
+    /// Number of measured runs per day, after a warm-up run
+    #[arg(long, default_value_t = 10)]
+    samples: usize,
+}
+
+pub fn bench(context: &Context, args: BenchArgs) -> Result<()> {
+    let year = Year(args.year);
+    let days = match args.day {
+        DaySelection::Single(day) => vec![day],
+        DaySelection::All => registered_days(year),
+    };
+    if days.is_empty() {
+        bail!("No days of {year} are solved");
+    }
+
+    let dir = match &context.config.bench_dir {
+        Some(dir) => dir.clone(),
+        None => workspace_root()
+            .ok_or_else(|| anyhow!("Failed to find the workspace root"))?
+            .join("bench"),
+    };
+    let mut history = BenchHistory::load(&dir, year)?;
+
+    let mut results = Vec::new();
+    for day in days {
+        let solver = solver(year, day).ok_or_else(|| anyhow!("{year} day {day} is not solved"))?;
+        let input = context.inputs.read(year, day)?;
+        results.push(benchmark(solver, day, &input, args.samples)?);
+    }
+
+    let run = BenchRun::new(results);
+    let baseline = history.baseline(&run.commit);
+    match baseline {
+        Some(baseline) => println!("{year} at {}, compared to {}", run.commit, baseline.commit),
+        None => println!("{year} at {}", run.commit),
+    }
+    println!(
+        "{:<6}{:<10}{:>10}{:>10}{:>10}{:>10}",
+        "Day", "Phase", "Median", "Min", "± Dev", "Change"
+    );
+    for bench in &run.days {
+        let previous = baseline.and_then(|baseline| baseline.day(Day(bench.day)));
+        let phases = [
+            (
+                "parse",
+                bench.parse,
+                previous.map(|previous| previous.parse),
+            ),
+            (
+                "part 1",
+                bench.part_one,
+                previous.map(|previous| previous.part_one),
+            ),
+            (
+                "part 2",
+                bench.part_two,
+                previous.map(|previous| previous.part_two),
+            ),
+        ];
+
+        for (phase, stats, previous) in phases {
+            println!(
+                "{:<6}{phase:<10}{:>10}{:>10}{:>10}{:>10}",
+                Day(bench.day).to_string(),
+                format_duration(stats.median),
+                format_duration(stats.min),
+                format_duration(stats.deviation),
+                previous.map_or(String::new(), |previous| change(previous, stats)),
+            );
+        }
+    }
+
+    history.push(run);
+    history.save()
+}
+
+fn change(previous: Stats, current: Stats) -> String {
+    let previous = previous.median.as_secs_f64();
+    if previous == 0.0 {
+        return String::new();
+    }
+
+    let change = (current.median.as_secs_f64() - previous) / previous * 100.0;
+
+    format!("{change:+.1}%")
+}
@@ -0,0 +1,70 @@
+use crate::Context;
+
+use clap::Args;
+use common::anyhow::{bail, Context as _};
+use common::{with_input_file, Day, Result, Year};
+use year_2015::day07::Circuit;
+
+use std::fs::read_to_string;
+
+#[derive(Debug, Args)]
+pub struct CircuitArgs {
+    /// Year of the event, e.g. 2015
+    year: u32,
+    /// Day (1-25)
+    day: Day,
+    /// Prints the circuit as a Graphviz DOT graph
+    #[arg(long, conflicts_with = "explain")]
+    dot: bool,
+    /// Labels the wires of the DOT graph with their signals
+    #[arg(long, requires = "dot")]
+    values: bool,
+    /// Prints the wires the signal of this wire is computed from, with their signals
+    #[arg(long, value_name = "WIRE")]
+    explain: Option<String>,
+}
+
+/// Inspects the wiring of a circuit; without `--dot` or `--explain`, lists the problems with it.
+pub fn circuit(context: &Context, args: CircuitArgs) -> Result<()> {
+    let (year, day) = (Year(args.year), args.day);
+    if (year, day) != (Year(2015), Day(7)) {
+        bail!("{year} day {day} has no circuit");
+    }
+    let path = context.inputs.locate(year, day)?;
+    let input =
+        read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let circuit = Circuit::<u16>::parse(input.lines().map(ToOwned::to_owned))
+        .map_err(|error| with_input_file(error, &path))?;
+
+    if args.dot {
+        let signals = args.values.then(|| circuit.evaluate_all()).transpose()?;
+        print!("{}", circuit.to_dot(signals.as_ref()));
+    } else if let Some(wire) = &args.explain {
+        print!("{}", circuit.explain(wire)?);
+    } else {
+        let diagnostics = circuit.validate(&["a"]);
+        if diagnostics.is_empty() {
+            println!("No problems found");
+        }
+        let errors = diagnostics
+            .iter()
+            .filter(|diagnostic| !diagnostic.is_warning())
+            .count();
+        for diagnostic in diagnostics {
+            let severity = if diagnostic.is_warning() {
+                "warning"
+            } else {
+                "error"
+            };
+            println!(
+                "{severity}: {}",
+                with_input_file(diagnostic.into_error(), &path)
+            );
+        }
+        if errors > 0 {
+            bail!("Found {errors} errors in the wiring");
+        }
+    }
+
+    Ok(())
+}
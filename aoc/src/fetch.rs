@@ -0,0 +1,34 @@
+use crate::{Context, DaySelection, Selection};
+
+use common::{fetch_input, unlock_time, Day, Fetched, Result, Year};
+
+use std::time::SystemTime;
+
+pub fn fetch(context: &Context, selection: Selection) -> Result<()> {
+    let year = Year(selection.year);
+    let client = context.client()?;
+
+    match selection.day {
+        DaySelection::Single(day) => {
+            report(year, day, fetch_input(&client, &context.inputs, year, day)?)
+        }
+        DaySelection::All => {
+            let unlocked = Day::all().filter(|&day| SystemTime::now() >= unlock_time(year, day));
+            for day in unlocked {
+                match fetch_input(&client, &context.inputs, year, day) {
+                    Ok(fetched) => report(year, day, fetched),
+                    Err(error) => println!("{year} day {day}: failed: {error:#}"),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn report(year: Year, day: Day, fetched: Fetched) {
+    match fetched {
+        Fetched::Cached(path) => println!("{year} day {day}: already at {}", path.display()),
+        Fetched::Downloaded(path) => println!("{year} day {day}: saved to {}", path.display()),
+    }
+}
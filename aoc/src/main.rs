@@ -0,0 +1,124 @@
+use clap::{Args, Parser, Subcommand};
+use common::anyhow::{bail, Error};
+use common::{Client, Config, Day, InputLocator, Result};
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// Solutions register themselves with `common::register!`, so the crates only need to be linked in.
+use year_2015 as _;
+
+mod bench;
+mod circuit;
+mod fetch;
+mod output;
+mod run;
+mod submit;
+mod verify;
+mod viz;
+
+/// Runs Advent of Code solutions
+///
+/// Without a subcommand, runs the selected days, e.g. `aoc 2015 7` or `aoc 2015 all`.
+#[derive(Debug, Parser)]
+#[command(subcommand_negates_reqs = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    selection: Option<Selection>,
+    /// Directory containing `<year>/day<NN>` input files
+    #[arg(long, global = true)]
+    input_dir: Option<PathBuf>,
+    /// Format of the answers printed by `run`
+    #[arg(long, global = true, value_enum, default_value_t)]
+    format: output::OutputFormat,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Runs solutions of the selected days
+    Run(Selection),
+    /// Downloads inputs of the selected days that aren't on disk yet
+    Fetch(Selection),
+    /// Submits an answer unless earlier verdicts already decide it
+    Submit(submit::SubmitArgs),
+    /// Runs the selected days repeatedly and compares their timings with the previous commit
+    Bench(bench::BenchArgs),
+    /// Checks answers of every solved day against `answers/<year>.toml`
+    Verify {
+        /// Year to verify, all years when omitted
+        year: Option<u32>,
+    },
+    /// Draws the steps of a day as images, an animated GIF or in the terminal
+    Viz(viz::VizArgs),
+    /// Checks the wiring of a circuit, draws it as a DOT graph or explains a wire's signal
+    Circuit(circuit::CircuitArgs),
+}
+
+#[derive(Debug, Clone, Copy, Args)]
+struct Selection {
+    /// Year of the event, e.g. 2015
+    year: u32,
+    /// Day (1-25) or `all`
+    day: DaySelection,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DaySelection {
+    All,
+    Single(Day),
+}
+
+impl FromStr for DaySelection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Self::All);
+        }
+
+        Ok(Self::Single(s.parse()?))
+    }
+}
+
+/// Everything the commands need besides their own arguments.
+struct Context {
+    config: Config,
+    inputs: InputLocator,
+}
+
+impl Context {
+    /// A client for the website that keeps the time of its last request next to the inputs.
+    fn client(&self) -> Result<Client> {
+        let client = Client::from_config(&self.config)?;
+
+        Ok(match self.inputs.primary_dir() {
+            Some(dir) => client.with_state_file(dir.join(Client::STATE_FILE_NAME)),
+            None => client,
+        })
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let config = Config::load()?;
+    let inputs = InputLocator::discover(cli.input_dir.as_deref(), &config);
+    let context = Context { config, inputs };
+
+    let command = match (cli.command, cli.selection) {
+        (Some(command), _) => command,
+        (None, Some(selection)) => Command::Run(selection),
+        (None, None) => bail!("Nothing to do, see `aoc --help`"),
+    };
+
+    match command {
+        Command::Run(selection) => run::run(&context, selection, cli.format),
+        Command::Fetch(selection) => fetch::fetch(&context, selection),
+        Command::Submit(args) => submit::submit(&context, args),
+        Command::Bench(args) => bench::bench(&context, args),
+        Command::Verify { year } => verify::verify(&context, year),
+        Command::Viz(args) => viz::viz(&context, args),
+        Command::Circuit(args) => circuit::circuit(&context, args),
+    }
+}
@@ -0,0 +1,131 @@
+use clap::ValueEnum;
+use common::{Answers, Day, Error, Part, Result, Year};
+use serde::Serialize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable answers followed by a timing summary
+    #[default]
+    Text,
+    /// A JSON array with one object per part
+    Json,
+    /// CSV with a header and one row per part
+    Csv,
+}
+
+/// The result of one part, as emitted in the structured formats.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub duration_ns: u64,
+}
+
+impl Record {
+    const CSV_HEADER: &'static str = "year,day,part,answer,error,duration_ns";
+
+    /// Records of both parts; a day that failed to run gets its error on both.
+    pub fn from_outcome(year: Year, day: Day, outcome: &Result<Answers>) -> [Record; 2] {
+        [Part::One, Part::Two].map(|part| {
+            let (result, duration) = match outcome {
+                Ok(answers) => match part {
+                    Part::One => (answers.part_one.as_ref(), answers.timings.part_one),
+                    Part::Two => (answers.part_two.as_ref(), answers.timings.part_two),
+                },
+                Err(error) => (Err(error), Default::default()),
+            };
+
+            Record {
+                year: year.0,
+                day: day.0,
+                part: part as u8,
+                answer: result.ok().cloned(),
+                error: result.err().map(|error: &Error| format!("{error:#}")),
+                duration_ns: duration.as_nanos() as u64,
+            }
+        })
+    }
+
+    fn csv_row(&self) -> String {
+        let fields = [
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.answer.as_deref().map(csv_field).unwrap_or_default(),
+            self.error.as_deref().map(csv_field).unwrap_or_default(),
+            self.duration_ns.to_string(),
+        ];
+
+        fields.join(",")
+    }
+}
+
+pub fn render(format: OutputFormat, records: &[Record]) -> Result<String> {
+    let rendered = match format {
+        OutputFormat::Text => unreachable!("text output is printed while running"),
+        OutputFormat::Json => serde_json::to_string_pretty(records)?,
+        OutputFormat::Csv => std::iter::once(Record::CSV_HEADER.to_string())
+            .chain(records.iter().map(Record::csv_row))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+
+    Ok(rendered)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2015,
+                day: 7,
+                part: 1,
+                answer: Some("3176".to_string()),
+                error: None,
+                duration_ns: 1500,
+            },
+            Record {
+                year: 2015,
+                day: 7,
+                part: 2,
+                answer: None,
+                error: Some("Failed, \"b\" is missing".to_string()),
+                duration_ns: 20,
+            },
+        ]
+    }
+
+    #[test]
+    fn renders_csv_with_quoted_fields() {
+        let expected = "year,day,part,answer,error,duration_ns\n\
+                        2015,7,1,3176,,1500\n\
+                        2015,7,2,,\"Failed, \"\"b\"\" is missing\",20";
+
+        assert_eq!(expected, render(OutputFormat::Csv, &records()).unwrap());
+    }
+
+    #[test]
+    fn renders_json_array() {
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json, &records()).unwrap()).unwrap();
+
+        assert_eq!(2, json.as_array().unwrap().len());
+        assert_eq!("3176", json[0]["answer"]);
+        assert_eq!(serde_json::Value::Null, json[1]["answer"]);
+        assert_eq!(20, json[1]["duration_ns"]);
+    }
+}
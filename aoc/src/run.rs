@@ -0,0 +1,107 @@
+use crate::output::{render, OutputFormat, Record};
+use crate::{Context, DaySelection, Selection};
+
+use common::anyhow::{anyhow, bail};
+use common::{
+    format_duration, missing_days, registered_days, solver, Answers, Day, Result, Timings, Year,
+};
+
+pub fn run(context: &Context, selection: Selection, format: OutputFormat) -> Result<()> {
+    let year = Year(selection.year);
+    let days = match selection.day {
+        DaySelection::Single(day) => {
+            solver(year, day).ok_or_else(|| anyhow!("{year} day {day} is not solved"))?;
+            vec![day]
+        }
+        DaySelection::All => registered_days(year),
+    };
+    if days.is_empty() {
+        bail!("No days of {year} are solved");
+    }
+
+    let mut records = Vec::new();
+    let mut timings = Vec::new();
+    for &day in &days {
+        let outcome = context.inputs.solve(year, day);
+
+        // A single day fails loudly, `all` keeps going and reports what failed.
+        if matches!(selection.day, DaySelection::Single(_)) && outcome.is_err() {
+            return outcome.map(|_| ());
+        }
+
+        match format {
+            OutputFormat::Text => match &outcome {
+                Ok(answers) => {
+                    print_answers(year, day, answers);
+                    timings.push((day, answers.timings));
+                }
+                Err(error) => println!("{year} day {day}\n  failed: {error:#}"),
+            },
+            OutputFormat::Json | OutputFormat::Csv => {
+                records.extend(Record::from_outcome(year, day, &outcome));
+            }
+        }
+    }
+
+    if format != OutputFormat::Text {
+        println!("{}", render(format, &records)?);
+    } else if matches!(selection.day, DaySelection::All) {
+        println!();
+        print_summary(&timings);
+
+        let missing = missing_days(year);
+        if !missing.is_empty() {
+            let missing = missing.iter().map(Day::to_string).collect::<Vec<_>>();
+            println!("Missing days: {}", missing.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+fn print_answers(year: Year, day: Day, answers: &Answers) {
+    let timings = &answers.timings;
+
+    println!(
+        "{year} day {day} (parsed in {})",
+        format_duration(timings.parse)
+    );
+    for (part, answer, time) in [
+        ("one", &answers.part_one, timings.part_one),
+        ("two", &answers.part_two, timings.part_two),
+    ] {
+        match answer {
+            Ok(answer) => println!("  part {part}: {answer} ({})", format_duration(time)),
+            Err(error) => println!("  part {part}: failed: {error:#}"),
+        }
+    }
+}
+
+fn print_summary(timings: &[(Day, Timings)]) {
+    let row = |name: &str, timings: &Timings| {
+        println!(
+            "{name:<6}{:>10}{:>10}{:>10}{:>10}",
+            format_duration(timings.parse),
+            format_duration(timings.part_one),
+            format_duration(timings.part_two),
+            format_duration(timings.total()),
+        );
+    };
+
+    println!(
+        "{:<6}{:>10}{:>10}{:>10}{:>10}",
+        "Day", "Parse", "Part 1", "Part 2", "Total"
+    );
+    for (day, timings) in timings {
+        row(&day.to_string(), timings);
+    }
+
+    let total = timings
+        .iter()
+        .fold(Timings::default(), |total, (_, timings)| Timings {
+            parse: total.parse + timings.parse,
+            part_one: total.part_one + timings.part_one,
+            part_two: total.part_two + timings.part_two,
+        });
+    row("Total", &total);
+}
@@ -0,0 +1,68 @@
+use crate::Context;
+
+use clap::Args;
+use common::anyhow::anyhow;
+use common::{submit_answer, Day, Part, Result, SubmitOutcome, Verdict, Verdicts, Year};
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    /// Year of the event, e.g. 2015
+    year: u32,
+    /// Day (1-25)
+    day: Day,
+    /// Part (1 or 2)
+    part: Part,
+    /// Answer to submit, computed by the solution when omitted
+    answer: Option<String>,
+}
+
+pub fn submit(context: &Context, args: SubmitArgs) -> Result<()> {
+    let (year, day, part) = (Year(args.year), args.day, args.part);
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => solve(context, year, day, part)?,
+    };
+
+    let input_dir = context
+        .inputs
+        .primary_dir()
+        .ok_or_else(|| anyhow!("No input directory to keep verdicts in"))?;
+    let mut verdicts = Verdicts::load(input_dir, year)?;
+    let bounds = verdicts.bounds(day, part);
+    if bounds.above.is_some() || bounds.below.is_some() {
+        println!("Known bounds: {bounds}");
+    }
+
+    let client = context.client()?;
+    let outcome = submit_answer(&client, &mut verdicts, year, day, part, &answer)?;
+
+    match outcome {
+        SubmitOutcome::Submitted(Verdict::Wait(remaining)) => {
+            let remaining = remaining.map_or("a while".to_string(), |remaining| {
+                format!("{}s", remaining.as_secs())
+            });
+            println!("{answer}: submitted too recently, wait {remaining} and try again");
+        }
+        SubmitOutcome::Submitted(verdict) => println!("{answer}: {verdict}"),
+        SubmitOutcome::AlreadyJudged(verdict) => {
+            println!("{answer}: not submitted, already judged as {verdict}")
+        }
+        SubmitOutcome::AlreadySolved(accepted) => {
+            println!("{answer}: not submitted, part {part} is already solved with {accepted}")
+        }
+        SubmitOutcome::OutOfBounds(bounds) => {
+            println!("{answer}: not submitted, outside of known bounds ({bounds})")
+        }
+    }
+
+    Ok(())
+}
+
+fn solve(context: &Context, year: Year, day: Day, part: Part) -> Result<String> {
+    let answers = context.inputs.solve(year, day)?;
+
+    match part {
+        Part::One => answers.part_one,
+        Part::Two => answers.part_two,
+    }
+}
@@ -0,0 +1,27 @@
+use crate::Context;
+
+use common::anyhow::{anyhow, bail};
+use common::{registered_years, verify_year, workspace_root, KnownAnswers, Result, Year};
+
+pub fn verify(context: &Context, year: Option<u32>) -> Result<()> {
+    let years = match year {
+        Some(year) => vec![Year(year)],
+        None => registered_years(),
+    };
+    let root = workspace_root().ok_or_else(|| anyhow!("Failed to find the workspace root"))?;
+
+    let mut success = true;
+    for year in years {
+        let known = KnownAnswers::load(&root.join("answers"), year)?;
+        let report = verify_year(&context.inputs, &known, year);
+        print!("{report}");
+
+        success &= report.is_success();
+    }
+
+    if !success {
+        bail!("Some answers differ from the known ones");
+    }
+
+    Ok(())
+}
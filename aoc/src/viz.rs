@@ -0,0 +1,120 @@
+use crate::Context;
+
+use clap::{Args, ValueEnum};
+use common::anyhow::{anyhow, Context as _};
+use common::viz::{visualizer, Frame, GifWriter, Recorder, Visualizer};
+use common::{Day, Part, Result, Year};
+
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::Duration;
+
+#[derive(Debug, Args)]
+pub struct VizArgs {
+    /// Year of the event, e.g. 2015
+    year: u32,
+    /// Day (1-25)
+    day: Day,
+    /// Part (1 or 2)
+    part: Part,
+    /// A `.gif` file for an animation or a directory for numbered frames, the terminal if omitted
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Image format of numbered frames
+    #[arg(long, value_enum, default_value_t)]
+    frame_format: FrameFormat,
+    /// Keeps only every n-th step, plus the final state
+    #[arg(long, default_value_t = 1)]
+    every: usize,
+    /// Averages every n by n block of cells into one pixel
+    #[arg(long, default_value_t = 1)]
+    shrink: usize,
+    /// Milliseconds every frame is shown for
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, ValueEnum)]
+enum FrameFormat {
+    #[default]
+    Png,
+    Ppm,
+}
+
+impl FrameFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            FrameFormat::Png => "png",
+            FrameFormat::Ppm => "ppm",
+        }
+    }
+}
+
+pub fn viz(context: &Context, args: VizArgs) -> Result<()> {
+    let (year, day) = (Year(args.year), args.day);
+    let visualizer =
+        visualizer(year, day).ok_or_else(|| anyhow!("{year} day {day} has no visualization"))?;
+    let input = context.inputs.read(year, day)?;
+    let delay = Duration::from_millis(args.delay);
+
+    match &args.output {
+        Some(path) if path.extension().is_some_and(|extension| extension == "gif") => {
+            let mut gif = GifWriter::new(BufWriter::new(create(path)?), delay);
+            let frames = record(visualizer, &input, &args, |frame| gif.push(&frame))?;
+            gif.finish()?.flush()?;
+            println!("Wrote {frames} frames to {}", path.display());
+        }
+        Some(dir) => {
+            create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+            let extension = args.frame_format.extension();
+            let mut written = 0;
+            let frames = record(visualizer, &input, &args, |frame| {
+                written += 1;
+                let path = dir.join(format!("frame-{written:05}.{extension}"));
+                let file = BufWriter::new(create(&path)?);
+                match args.frame_format {
+                    FrameFormat::Png => frame.write_png(file),
+                    FrameFormat::Ppm => frame.write_ppm(file),
+                }
+            })?;
+            println!("Wrote {frames} frames to {}", dir.display());
+        }
+        None => {
+            record(visualizer, &input, &args, |frame| {
+                // Clears the screen so the frames replace each other.
+                print!("\x1b[H\x1b[2J{}", frame.to_ansi());
+                sleep(delay);
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the visualizer, passing every kept frame to `sink`, and returns the number of frames.
+fn record(
+    visualizer: Visualizer,
+    input: &str,
+    args: &VizArgs,
+    mut sink: impl FnMut(Frame) -> Result<()>,
+) -> Result<usize> {
+    let mut frames = 0;
+    let mut shrunk = |frame: Frame| {
+        frames += 1;
+        sink(frame.shrink(args.shrink))
+    };
+    visualizer(
+        input,
+        args.part,
+        &mut Recorder::new(args.every, &mut shrunk),
+    )?;
+
+    Ok(frames)
+}
+
+fn create(path: &Path) -> Result<File> {
+    File::create(path).with_context(|| format!("Failed to create {}", path.display()))
+}
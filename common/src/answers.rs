@@ -0,0 +1,73 @@
+use crate::{Day, Part, Result, Year};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct DayAnswers {
+    one: Option<String>,
+    two: Option<String>,
+}
+
+/// Accepted answers of one year, stored as `answers/<year>.toml` in the workspace root:
+///
+/// ```toml
+/// [day07]
+/// one = "3176"
+/// two = "14710"
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KnownAnswers {
+    days: BTreeMap<String, DayAnswers>,
+}
+
+impl KnownAnswers {
+    pub fn path(dir: &Path, year: Year) -> PathBuf {
+        dir.join(format!("{year}.toml"))
+    }
+
+    pub fn load(dir: &Path, year: Year) -> Result<Self> {
+        let path = Self::path(dir, year);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read known answers from {}", path.display()))?;
+
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn get(&self, day: Day, part: Part) -> Option<&str> {
+        let answers = self.days.get(&format!("day{day}"))?;
+        let answer = match part {
+            Part::One => &answers.one,
+            Part::Two => &answers.two,
+        };
+
+        answer.as_deref()
+    }
+
+    pub fn insert(&mut self, day: Day, part: Part, answer: impl Into<String>) {
+        let answers = self.days.entry(format!("day{day}")).or_default();
+        let answer = Some(answer.into());
+        match part {
+            Part::One => answers.one = answer,
+            Part::Two => answers.two = answer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_answers_by_day_and_part() {
+        let answers: KnownAnswers = toml::from_str("[day07]\none = \"3176\"\n").unwrap();
+
+        assert_eq!(Some("3176"), answers.get(Day(7), Part::One));
+        assert_eq!(None, answers.get(Day(7), Part::Two));
+        assert_eq!(None, answers.get(Day(8), Part::One));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Day, Result, Solver, Stats, Year};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayBench {
+    pub day: u8,
+    pub parse: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+}
+
+/// Runs the solver `samples` times after a single warm-up run.
+pub fn benchmark(solver: Solver, day: Day, input: &str, samples: usize) -> Result<DayBench> {
+    solver(input)?;
+
+    let mut parse = Vec::with_capacity(samples);
+    let mut part_one = Vec::with_capacity(samples);
+    let mut part_two = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let timings = solver(input)?.timings;
+        parse.push(timings.parse);
+        part_one.push(timings.part_one);
+        part_two.push(timings.part_two);
+    }
+
+    Ok(DayBench {
+        day: day.0,
+        parse: Stats::from_samples(&parse),
+        part_one: Stats::from_samples(&part_one),
+        part_two: Stats::from_samples(&part_two),
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchRun {
+    /// Short hash of the benchmarked commit, with a `-dirty` suffix for uncommitted changes.
+    pub commit: String,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub days: Vec<DayBench>,
+}
+
+impl BenchRun {
+    pub fn new(days: Vec<DayBench>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO)
+            .as_secs();
+
+        Self {
+            commit: current_commit().unwrap_or_else(|| "unknown".to_string()),
+            timestamp,
+            days,
+        }
+    }
+
+    pub fn day(&self, day: Day) -> Option<&DayBench> {
+        self.days.iter().find(|bench| bench.day == day.0)
+    }
+}
+
+/// Every benchmark run of one year, stored as `target/aoc-bench/<year>.toml` in the workspace.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BenchHistory {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    runs: Vec<BenchRun>,
+}
+
+impl BenchHistory {
+    pub fn load(dir: &Path, year: Year) -> Result<Self> {
+        let path = dir.join(format!("{year}.toml"));
+        let mut history: Self = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(error) => return Err(error.into()),
+        };
+        history.path = path;
+
+        Ok(history)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, toml::to_string(self)?)?;
+
+        Ok(())
+    }
+
+    /// The latest run of a commit other than the given one, to compare a new run against.
+    pub fn baseline(&self, commit: &str) -> Option<&BenchRun> {
+        self.runs.iter().rev().find(|run| run.commit != commit)
+    }
+
+    pub fn push(&mut self, run: BenchRun) {
+        self.runs.push(run);
+    }
+}
+
+fn current_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        let output = Command::new("git").args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let dirty = !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+
+    Some(if dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
@@ -0,0 +1,182 @@
+use std::fmt::{self, Display};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A dense grid of booleans packed into 64 bit words, each row starting with a new word.
+///
+/// Positions are `(x, y)` like in a [`Grid`](crate::Grid). Rectangle updates work on whole words
+/// of a row at a time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid with every bit cleared.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<bool> {
+        self.contains(position).then(|| {
+            let (word, bit) = self.locate(position);
+
+            self.words[word] & bit != 0
+        })
+    }
+
+    /// # Panics
+    ///
+    /// If the position is outside of the grid.
+    pub fn set(&mut self, position: (usize, usize), value: bool) {
+        assert!(
+            self.contains(position),
+            "{position:?} is outside of the grid"
+        );
+        let (word, bit) = self.locate(position);
+
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    /// Sets every bit of the rectangle spanned by two corners, both inclusive.
+    pub fn set_rectangle(&mut self, corner: (usize, usize), opposite: (usize, usize)) {
+        self.update_rectangle(corner, opposite, |word, mask| word | mask);
+    }
+
+    /// Clears every bit of the rectangle spanned by two corners, both inclusive.
+    pub fn clear_rectangle(&mut self, corner: (usize, usize), opposite: (usize, usize)) {
+        self.update_rectangle(corner, opposite, |word, mask| word & !mask);
+    }
+
+    /// Flips every bit of the rectangle spanned by two corners, both inclusive.
+    pub fn toggle_rectangle(&mut self, corner: (usize, usize), opposite: (usize, usize)) {
+        self.update_rectangle(corner, opposite, |word, mask| word ^ mask);
+    }
+
+    /// Number of set bits.
+    pub fn count_ones(&self) -> u64 {
+        self.words
+            .iter()
+            .map(|word| u64::from(word.count_ones()))
+            .sum()
+    }
+
+    /// Draws the grid with `#` for set and `.` for cleared bits.
+    pub fn render(&self) -> String {
+        let mut rendered = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (word, bit) = self.locate((x, y));
+                rendered.push(if self.words[word] & bit != 0 {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+
+    fn locate(&self, (x, y): (usize, usize)) -> (usize, u64) {
+        (y * self.words_per_row + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    /// # Panics
+    ///
+    /// If the rectangle reaches outside of the grid.
+    fn update_rectangle(
+        &mut self,
+        (x1, y1): (usize, usize),
+        (x2, y2): (usize, usize),
+        update: impl Fn(u64, u64) -> u64,
+    ) {
+        let (left, right) = (x1.min(x2), x1.max(x2));
+        let (top, bottom) = (y1.min(y2), y1.max(y2));
+        assert!(
+            self.contains((right, bottom)),
+            "{:?} is outside of the grid",
+            (right, bottom)
+        );
+
+        let (first, last) = (left / WORD_BITS, right / WORD_BITS);
+        for y in top..=bottom {
+            let row = y * self.words_per_row;
+            for index in first..=last {
+                let low = if index == first { left % WORD_BITS } else { 0 };
+                let high = if index == last {
+                    right % WORD_BITS
+                } else {
+                    WORD_BITS - 1
+                };
+                let mask = (u64::MAX << low) & (u64::MAX >> (WORD_BITS - 1 - high));
+
+                let word = &mut self.words[row + index];
+                *word = update(*word, mask);
+            }
+        }
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_rectangles_across_words() {
+        let mut grid = BitGrid::new(200, 3);
+        grid.set_rectangle((10, 0), (150, 2));
+        grid.clear_rectangle((64, 1), (127, 1));
+        grid.toggle_rectangle((0, 2), (199, 2));
+
+        assert_eq!(141 + 141 - 64 + 200 - 141, grid.count_ones());
+        assert_eq!(Some(true), grid.get((63, 1)));
+        assert_eq!(Some(false), grid.get((64, 1)));
+        assert_eq!(Some(true), grid.get((9, 2)));
+        assert_eq!(Some(false), grid.get((10, 2)));
+        assert_eq!(None, grid.get((200, 0)));
+    }
+
+    #[test]
+    fn sets_single_bits_and_renders() {
+        let mut grid = BitGrid::new(3, 2);
+        grid.set((1, 0), true);
+        grid.set((2, 1), true);
+        grid.set((2, 1), false);
+
+        assert_eq!(".#.\n...\n", grid.to_string());
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{Config, Day, Part, Result, Verdict, Year};
+
+use anyhow::{anyhow, bail, Context};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime};
+
+/// HTTP client for the Advent of Code website, or anything serving the same endpoints.
+///
+/// Requests are spaced at least `min_interval` apart so fetching many days at once doesn't
+/// hammer the server. With a state file, the time of the last request is shared by every run
+/// of the CLI.
+#[derive(Debug)]
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Mutex<Option<SystemTime>>,
+    /// Keeps the time of the last request, in milliseconds since the Unix epoch.
+    state_file: Option<PathBuf>,
+}
+
+impl Client {
+    pub const DEFAULT_BASE_URL: &'static str = "https://adventofcode.com";
+    pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(3);
+    pub const STATE_FILE_NAME: &'static str = ".last-request";
+    const USER_AGENT: &'static str = "github.com/isenar/advent-of-code by dariusz.dajka@gmail.com";
+
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(Self::USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            min_interval: Self::DEFAULT_MIN_INTERVAL,
+            last_request: Mutex::new(None),
+            state_file: None,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let session = config
+            .session
+            .as_deref()
+            .ok_or_else(|| anyhow!("No session token, set `session` in {}", Config::FILE_NAME))?;
+        let base_url = config.base_url.as_deref().unwrap_or(Self::DEFAULT_BASE_URL);
+        let mut client = Self::new(base_url, session);
+
+        if let Some(seconds) = config.request_interval {
+            client = client.with_min_interval(Duration::from_secs_f64(seconds));
+        }
+
+        Ok(client)
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+
+        self
+    }
+
+    pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_file = Some(path.into());
+
+        self
+    }
+
+    pub fn download_input(&self, year: Year, day: Day) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day.0);
+        let request = self.agent.get(&url);
+
+        self.send(request, None)
+    }
+
+    pub fn submit_answer(&self, year: Year, day: Day, part: Part, answer: &str) -> Result<Verdict> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day.0);
+        let request = self.agent.post(&url);
+        let level = (part as u8).to_string();
+        let response = self.send(request, Some(&[("level", &level), ("answer", answer)]))?;
+
+        Verdict::parse(&response).ok_or_else(|| anyhow!("Unrecognized response from {url}"))
+    }
+
+    fn send(&self, request: ureq::Request, form: Option<&[(&str, &str)]>) -> Result<String> {
+        self.wait_for_turn()?;
+
+        let url = request.url().to_string();
+        let request = request.set("Cookie", &format!("session={}", self.session));
+
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(429, _)) => bail!("Rate limited by {url}, try again later"),
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                bail!(
+                    "Request to {url} failed with status {code}: {}",
+                    body.trim()
+                )
+            }
+            Err(error) => Err(error).with_context(|| format!("Request to {url} failed")),
+        }
+    }
+
+    fn wait_for_turn(&self) -> Result<()> {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        let last = last_request.into_iter().chain(self.stored_request()).max();
+        if let Some(last) = last {
+            // A clock that went backwards counts as a request just now.
+            let elapsed = last.elapsed().unwrap_or_default();
+            if let Some(remaining) = self.min_interval.checked_sub(elapsed) {
+                sleep(remaining);
+            }
+        }
+
+        let now = SystemTime::now();
+        *last_request = Some(now);
+        if let Some(path) = &self.state_file {
+            let millis = now.duration_since(SystemTime::UNIX_EPOCH)?.as_millis();
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, millis.to_string())
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    /// The last request recorded in the state file, ignoring a missing or unreadable one.
+    fn stored_request(&self) -> Option<SystemTime> {
+        let content = fs::read_to_string(self.state_file.as_ref()?).ok()?;
+        let millis = content.trim().parse().ok()?;
+
+        Some(SystemTime::UNIX_EPOCH + Duration::from_millis(millis))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_server::{TempDir, TestServer};
+    use std::time::Instant;
+
+    #[test]
+    fn spaces_requests_across_clients_sharing_a_state_file() {
+        let server = TestServer::start(vec![(200, "1".to_string()), (200, "2".to_string())]);
+        let dir = TempDir::new("client-state");
+        let state_file = dir.join(Client::STATE_FILE_NAME);
+        let client = || {
+            Client::new(server.url(), "secret")
+                .with_min_interval(Duration::from_millis(300))
+                .with_state_file(&state_file)
+        };
+
+        client().download_input(Year(2015), Day(1)).unwrap();
+        let start = Instant::now();
+        client().download_input(Year(2015), Day(2)).unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(250));
+        assert!(state_file.is_file());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::Result;
+
+use anyhow::Context;
+use serde::Deserialize;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+/// Settings read from an `aoc.toml` file.
+///
+/// The file is looked up at `$AOC_CONFIG` first and then in the current directory and its
+/// ancestors. Relative paths inside the file are relative to the file itself.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Config {
+    pub input_dir: Option<PathBuf>,
+    /// Value of the `session` cookie of a logged in adventofcode.com user.
+    pub session: Option<String>,
+    /// Defaults to [`Client::DEFAULT_BASE_URL`](crate::Client::DEFAULT_BASE_URL).
+    pub base_url: Option<String>,
+    /// Minimum number of seconds between two requests.
+    pub request_interval: Option<f64>,
+    /// Where `aoc bench` keeps its history, `bench/` in the workspace root by default.
+    pub bench_dir: Option<PathBuf>,
+}
+
+impl Config {
+    pub const FILE_NAME: &'static str = "aoc.toml";
+    pub const PATH_VARIABLE: &'static str = "AOC_CONFIG";
+
+    pub fn load() -> Result<Self> {
+        match Self::find() {
+            Some(path) => Self::from_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let mut config: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+
+        if let Some(base) = path.parent() {
+            for dir in [&mut config.input_dir, &mut config.bench_dir] {
+                if let Some(relative) = dir.as_mut() {
+                    *relative = base.join(&*relative);
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn find() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os(Self::PATH_VARIABLE) {
+            return Some(PathBuf::from(path));
+        }
+
+        let current_dir = std::env::current_dir().ok()?;
+        current_dir
+            .ancestors()
+            .map(|dir| dir.join(Self::FILE_NAME))
+            .find(|path| path.is_file())
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{Day, InputSource, Year};
+
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+
+/// Where in an input something went wrong. Lines and columns start at 1.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}", file.display())?,
+            None => f.write_str("input")?,
+        }
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, ":{line}:{column}"),
+            (Some(line), None) => write!(f, ":{line}"),
+            // Without a line, a bare number would read as one.
+            (None, Some(column)) => write!(f, ":col {column}"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Problems with finding or parsing a puzzle input.
+///
+/// Parsers return [`InputError::Invalid`] for the text they reject, and the location is filled in
+/// on the way up: [`parse_lines`] adds the line and the runner adds the file.
+#[derive(Debug)]
+pub enum InputError {
+    NotFound {
+        year: Year,
+        day: Day,
+        tried: Vec<(InputSource, PathBuf)>,
+    },
+    Invalid {
+        location: Location,
+        /// The offending part of the input.
+        text: String,
+        reason: String,
+    },
+}
+
+impl InputError {
+    pub fn invalid(text: impl Into<String>, reason: impl Display) -> Self {
+        Self::Invalid {
+            location: Location::default(),
+            text: text.into(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Places an error of parsing `inner` inside the `outer` text it was taken from, adjusting
+    /// the column accordingly.
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        if let Self::Invalid { location, .. } = &mut self {
+            if let Some(offset) = column_of(outer, inner) {
+                location.column = Some(offset + location.column.unwrap_or(1) - 1);
+            }
+        }
+
+        self
+    }
+
+    pub fn at_line(mut self, line_number: usize, line: &str) -> Self {
+        if let Self::Invalid { location, text, .. } = &mut self {
+            location.line = Some(line_number);
+            if location.column.is_none() {
+                location.column = column_of(line, text);
+            }
+        }
+
+        self
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Self::Invalid { location, .. } => Some(location),
+            Self::NotFound { .. } => None,
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { year, day, tried } => {
+                write!(f, "Input for {year} day {day} not found, tried:")?;
+                for (source, path) in tried {
+                    write!(f, "\n  {} (from {source})", path.display())?;
+                }
+
+                Ok(())
+            }
+            Self::Invalid {
+                location,
+                text,
+                reason,
+            } => write!(f, "{location}: {reason}: '{text}'"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Attaches the input file to an [`InputError`] carried by `error`, if there is one.
+pub fn with_input_file(mut error: anyhow::Error, path: impl Into<PathBuf>) -> anyhow::Error {
+    if let Some(InputError::Invalid { location, .. }) = error.downcast_mut::<InputError>() {
+        location.file = Some(path.into());
+    }
+
+    error
+}
+
+/// Parses every line of the input, reporting the line number of the first one that fails.
+pub fn parse_lines<T>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> Result<T, InputError>,
+) -> Result<Vec<T>, InputError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|error| error.at_line(index + 1, line)))
+        .collect()
+}
+
+/// 1-based column at which `inner` starts within `outer`.
+///
+/// `inner` is expected to be a slice of `outer`; otherwise its first occurrence is used.
+pub fn column_of(outer: &str, inner: &str) -> Option<usize> {
+    let outer_start = outer.as_ptr() as usize;
+    let inner_start = inner.as_ptr() as usize;
+
+    let offset = if (outer_start..=outer_start + outer.len()).contains(&inner_start)
+        && inner_start + inner.len() <= outer_start + outer.len()
+    {
+        inner_start - outer_start
+    } else {
+        outer.find(inner)?
+    };
+
+    Some(outer[..offset].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_number(line: &str) -> Result<u32, InputError> {
+        let (_, number) = line.split_once(' ').unwrap_or(("", line));
+
+        number
+            .parse()
+            .map_err(|error| InputError::invalid(number, error).within(line, number))
+    }
+
+    #[test]
+    fn reports_file_line_and_column() {
+        let error = parse_lines("x 12\ny 34\nz 5a6", parse_number).unwrap_err();
+        let error = with_input_file(error.into(), "input/2015/day02");
+
+        assert_eq!(
+            "input/2015/day02:3:3: invalid digit found in string: '5a6'",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn labels_a_column_without_a_line() {
+        let error = parse_number("z 5a6").unwrap_err();
+
+        assert_eq!(
+            "input:col 3: invalid digit found in string: '5a6'",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn finds_column_of_a_slice_even_if_text_repeats() {
+        let line = "toggle 1,1 through 1,1";
+        let second = &line[19..];
+
+        assert_eq!(Some(20), column_of(line, second));
+        assert_eq!(Some(8), column_of(line, "1,1"));
+    }
+}
@@ -0,0 +1,52 @@
+/// Generates a test per worked example of a puzzle statement.
+///
+/// Each part gets its own module with one `#[test]` per example, which parses the example input
+/// with the given [`Solution`](crate::Solution) and compares that part's answer with the expected
+/// one:
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     common::examples! {
+///         Day03,
+///         part_one {
+///             simple: ">" => 2,
+///             houses_in_a_square: "^>v<" => 4,
+///         }
+///         part_two {
+///             robo_santa: "^v" => 3,
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! examples {
+    (
+        $solution:ty,
+        $(
+            $part:ident {
+                $( $name:ident : $input:expr => $expected:expr ),* $(,)?
+            }
+        )*
+    ) => {
+        $(
+            mod $part {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let input = <$solution as $crate::Solution>::parse($input)
+                            .expect("example input should parse");
+                        let answer = <$solution as $crate::Solution>::$part(&input)
+                            .expect("example should be solved");
+
+                        assert_eq!($expected, answer);
+                    }
+                )*
+            }
+        )*
+    };
+}
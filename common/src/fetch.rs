@@ -0,0 +1,117 @@
+use crate::{input_path, Client, Day, InputLocator, Result, Year};
+
+use anyhow::{anyhow, bail};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already on disk and no request was made.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the input of the given day is on disk, downloading it only if no input directory
+/// has it yet. Downloads are stored in the locator's primary directory.
+pub fn fetch_input(
+    client: &Client,
+    inputs: &InputLocator,
+    year: Year,
+    day: Day,
+) -> Result<Fetched> {
+    if let Ok(path) = inputs.locate(year, day) {
+        return Ok(Fetched::Cached(path));
+    }
+
+    if SystemTime::now() < unlock_time(year, day) {
+        bail!("{year} day {day} is not unlocked yet");
+    }
+
+    let dir = inputs
+        .primary_dir()
+        .ok_or_else(|| anyhow!("No input directory to store {year} day {day} in"))?;
+    let input = client.download_input(year, day)?;
+
+    let path = input_path(dir, year, day);
+    let partial = path.with_extension("part");
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&partial, input)?;
+    fs::rename(&partial, &path)?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+/// Puzzles unlock at midnight EST (UTC-5) on the given day of December.
+pub fn unlock_time(year: Year, day: Day) -> SystemTime {
+    const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+    const EST_OFFSET: u64 = 5 * 60 * 60;
+
+    let days = days_since_epoch(year.0 as i64, 12, day.0 as i64).max(0) as u64;
+
+    SystemTime::UNIX_EPOCH + Duration::from_secs(days * SECONDS_PER_DAY + EST_OFFSET)
+}
+
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{TempDir, TestServer};
+    use crate::InputSource;
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        let unlock = unlock_time(Year(2015), Day(1));
+        let since_epoch = unlock.duration_since(SystemTime::UNIX_EPOCH).unwrap();
+
+        // 2015-12-01T05:00:00Z
+        assert_eq!(1_448_946_000, since_epoch.as_secs());
+    }
+
+    #[test]
+    fn downloads_once_and_then_uses_the_cache() {
+        let server = TestServer::start(vec![(200, "(()(\n".to_string())]);
+        let client = Client::new(server.url(), "secret").with_min_interval(Duration::ZERO);
+        let dir = TempDir::new("fetch-cache");
+        let inputs = InputLocator::default().with_dir(InputSource::Flag, dir.path());
+        let path = dir.join("2015/day01");
+
+        let first = fetch_input(&client, &inputs, Year(2015), Day(1)).unwrap();
+        let second = fetch_input(&client, &inputs, Year(2015), Day(1)).unwrap();
+
+        assert_eq!(Fetched::Downloaded(path.clone()), first);
+        assert_eq!(Fetched::Cached(path.clone()), second);
+        assert_eq!("(()(\n", fs::read_to_string(path).unwrap());
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        assert!(requests[0].starts_with("GET /2015/day/1/input "));
+        assert!(requests[0].contains("session=secret"));
+    }
+
+    #[test]
+    fn does_not_cache_failed_downloads() {
+        let server = TestServer::start(vec![(400, "Please log in".to_string())]);
+        let client = Client::new(server.url(), "expired").with_min_interval(Duration::ZERO);
+        let dir = TempDir::new("fetch-failed");
+        let inputs = InputLocator::default().with_dir(InputSource::Flag, dir.path());
+
+        let error = fetch_input(&client, &inputs, Year(2015), Day(2)).unwrap_err();
+
+        assert!(error.to_string().contains("status 400: Please log in"));
+        assert!(!dir.join("2015/day02").exists());
+    }
+}
@@ -0,0 +1,597 @@
+//! Signed coordinates, directions and a sparse grid for puzzles on an unbounded plane.
+//!
+//! The `y` axis grows downwards, like rows of a [`Grid`](crate::Grid), so [`Direction::Up`]
+//! decreases `y`.
+
+use crate::InputError;
+
+use std::collections::hash_map::{self, HashMap};
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.vector()
+    }
+
+    /// Converts to `(x, y)` position of a [`Grid`](crate::Grid), if neither is negative.
+    pub fn to_grid(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Parses `x,y`.
+impl FromStr for Point {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match *parse_coordinates(s)?.as_slice() {
+            [x, y] => Ok(Self::new(x, y)),
+            _ => Err(InputError::invalid(s, "Expected 'x,y'")),
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// Parses `x,y,z`.
+impl FromStr for Point3 {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match *parse_coordinates(s)?.as_slice() {
+            [x, y, z] => Ok(Self::new(x, y, z)),
+            _ => Err(InputError::invalid(s, "Expected 'x,y,z'")),
+        }
+    }
+}
+
+fn parse_coordinates(s: &str) -> Result<Vec<i64>, InputError> {
+    s.split(',')
+        .map(|coordinate| {
+            let trimmed = coordinate.trim();
+
+            trimmed
+                .parse()
+                .map_err(|error| InputError::invalid(trimmed, error).within(s, trimmed))
+        })
+        .collect()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting with [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn vector(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Parses arrows (`^v<>`), compass points (`NSEW`) and letters (`UDLR`).
+    pub fn from_char(symbol: char) -> Option<Self> {
+        let direction = match symbol {
+            '^' | 'N' | 'U' => Direction::Up,
+            '>' | 'E' | 'R' => Direction::Right,
+            'v' | 'S' | 'D' => Direction::Down,
+            '<' | 'W' | 'L' => Direction::Left,
+            _ => return None,
+        };
+
+        Some(direction)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = InputError;
+
+    fn try_from(symbol: char) -> Result<Self, Self::Error> {
+        Self::from_char(symbol)
+            .ok_or_else(|| InputError::invalid(symbol, "Expected one of '^v<>', 'NSEW' or 'UDLR'"))
+    }
+}
+
+/// A range of integers with both ends inclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    /// The interval between two ends, in any order.
+    pub fn new(start: i64, end: i64) -> Self {
+        Self {
+            start: start.min(end),
+            end: start.max(end),
+        }
+    }
+
+    pub fn length(&self) -> u64 {
+        self.start.abs_diff(self.end) + 1
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        (self.start..=self.end).contains(&value)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (start, end) = (self.start.max(other.start), self.end.min(other.end));
+
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// The smallest interval containing both.
+    pub fn hull(&self, other: &Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+/// An axis aligned rectangle between two corners, both inclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// The rectangle spanned by two opposite corners, in any order.
+    pub fn new(corner: Point, opposite: Point) -> Self {
+        Self {
+            min: Point::new(corner.x.min(opposite.x), corner.y.min(opposite.y)),
+            max: Point::new(corner.x.max(opposite.x), corner.y.max(opposite.y)),
+        }
+    }
+
+    pub fn from_spans(columns: Interval, rows: Interval) -> Self {
+        Self {
+            min: Point::new(columns.start, rows.start),
+            max: Point::new(columns.end, rows.end),
+        }
+    }
+
+    /// The smallest rectangle containing all of the points, if there are any.
+    pub fn bounding(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(Self::new(first, first), Self::extended))
+    }
+
+    pub fn extended(self, point: Point) -> Self {
+        self.hull(&Self::new(point, point))
+    }
+
+    pub fn columns(&self) -> Interval {
+        Interval::new(self.min.x, self.max.x)
+    }
+
+    pub fn rows(&self) -> Interval {
+        Interval::new(self.min.y, self.max.y)
+    }
+
+    pub fn width(&self) -> u64 {
+        self.columns().length()
+    }
+
+    pub fn height(&self) -> u64 {
+        self.rows().length()
+    }
+
+    pub fn area(&self) -> u64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.columns().contains(point.x) && self.rows().contains(point.y)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let columns = self.columns().intersection(&other.columns())?;
+        let rows = self.rows().intersection(&other.rows())?;
+
+        Some(Self::from_spans(columns, rows))
+    }
+
+    /// The smallest rectangle containing both.
+    pub fn hull(&self, other: &Self) -> Self {
+        Self::from_spans(
+            self.columns().hull(&other.columns()),
+            self.rows().hull(&other.rows()),
+        )
+    }
+
+    /// Splits off the parts not covered by `other`, as at most four disjoint rectangles.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let Some(overlap) = self.intersection(other) else {
+            return vec![*self];
+        };
+        let (columns, rows) = (self.columns(), overlap.rows());
+
+        let pieces = [
+            (columns, Interval::new(self.min.y, overlap.min.y - 1)),
+            (columns, Interval::new(overlap.max.y + 1, self.max.y)),
+            (Interval::new(self.min.x, overlap.min.x - 1), rows),
+            (Interval::new(overlap.max.x + 1, self.max.x), rows),
+        ];
+        let bounded = [
+            overlap.min.y > self.min.y,
+            overlap.max.y < self.max.y,
+            overlap.min.x > self.min.x,
+            overlap.max.x < self.max.x,
+        ];
+
+        pieces
+            .into_iter()
+            .zip(bounded)
+            .filter(|&(_, bounded)| bounded)
+            .map(|((columns, rows), _)| Self::from_spans(columns, rows))
+            .collect()
+    }
+
+    /// Disjoint rectangles covering exactly both.
+    pub fn union(&self, other: &Self) -> Vec<Self> {
+        let mut pieces = vec![*self];
+        pieces.extend(other.difference(self));
+
+        pieces
+    }
+
+    /// Every point of the rectangle, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let Self { min, max } = *self;
+
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+/// A grid of unbounded size that only stores the occupied cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    pub fn entry(&mut self, point: Point) -> hash_map::Entry<'_, Point, T> {
+        self.cells.entry(point)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// Occupied cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// The smallest rectangle containing every occupied cell.
+    pub fn bounds(&self) -> Option<Rect> {
+        Rect::bounding(self.cells.keys().copied())
+    }
+
+    /// Draws the occupied area with one character per cell, passing `None` for empty cells.
+    pub fn render(&self, mut symbol: impl FnMut(Option<&T>) -> char) -> String {
+        let Some(bounds) = self.bounds() else {
+            return String::new();
+        };
+
+        let mut rendered = String::new();
+        for point in bounds.points() {
+            rendered.push(symbol(self.get(point)));
+            if point.x == bounds.max.x {
+                rendered.push('\n');
+            }
+        }
+
+        rendered
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_points() {
+        assert_eq!(Point::new(-3, 14), "-3,14".parse().unwrap());
+        assert_eq!(Point3::new(1, 2, 3), "1, 2, 3".parse().unwrap());
+
+        let error = "1,x".parse::<Point>().unwrap_err();
+        assert_eq!(
+            "input:col 3: invalid digit found in string: 'x'",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn measures_manhattan_distance() {
+        assert_eq!(7, Point::new(1, -2).manhattan_distance(Point::new(-2, 2)));
+        assert_eq!(6, Point3::ORIGIN.manhattan_distance(Point3::new(1, -2, 3)));
+    }
+
+    #[test]
+    fn parses_and_rotates_directions() {
+        assert_eq!(Some(Direction::Up), Direction::from_char('^'));
+        assert_eq!(Some(Direction::Left), Direction::from_char('W'));
+        assert_eq!(Some(Direction::Down), Direction::from_char('D'));
+        assert!(Direction::try_from('x').is_err());
+
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+        assert_eq!(Direction::Left, Direction::Up.turn_left());
+        assert_eq!(Direction::Down, Direction::Up.opposite());
+        assert_eq!(Point::new(0, -2), Point::ORIGIN.step(Direction::Up) * 2);
+    }
+
+    #[test]
+    fn bounds_points() {
+        let points = [Point::new(2, -1), Point::new(-1, 3), Point::new(0, 0)];
+        let bounds = Rect::bounding(points).unwrap();
+
+        assert_eq!(Rect::new(Point::new(-1, -1), Point::new(2, 3)), bounds);
+        assert_eq!(20, bounds.area());
+        assert!(points.iter().all(|&point| bounds.contains(point)));
+    }
+
+    #[test]
+    fn intersects_intervals() {
+        let interval = Interval::new(5, 1);
+
+        assert_eq!(5, interval.length());
+        assert_eq!(
+            Some(Interval::new(3, 5)),
+            interval.intersection(&Interval::new(3, 9))
+        );
+        assert_eq!(None, interval.intersection(&Interval::new(6, 9)));
+        assert_eq!(Interval::new(1, 9), interval.hull(&Interval::new(7, 9)));
+    }
+
+    #[test]
+    fn splits_and_joins_rectangles() {
+        let outer = Rect::new(Point::new(0, 0), Point::new(9, 9));
+        let inner = Rect::new(Point::new(3, 3), Point::new(5, 12));
+
+        assert_eq!(
+            Some(Rect::new(Point::new(3, 3), Point::new(5, 9))),
+            outer.intersection(&inner)
+        );
+
+        let pieces = outer.difference(&inner);
+        assert_eq!(3, pieces.len());
+        assert_eq!(100 - 21, pieces.iter().map(Rect::area).sum::<u64>());
+        assert!(pieces.iter().all(|piece| !piece.overlaps(&inner)));
+
+        let union = outer.union(&inner);
+        assert_eq!(100 + 9, union.iter().map(Rect::area).sum::<u64>());
+        assert_eq!(vec![outer], outer.union(&outer));
+    }
+
+    #[test]
+    fn renders_occupied_area() {
+        let grid: SparseGrid<_> = [(Point::new(-1, 0), 'a'), (Point::new(1, 1), 'b')]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            "a..\n..b\n",
+            grid.render(|cell| cell.copied().unwrap_or('.'))
+        );
+    }
+}
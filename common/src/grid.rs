@@ -0,0 +1,383 @@
+use crate::InputError;
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// Which cells count as neighbors of a cell.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left and right.
+    Four,
+    /// Also the diagonals.
+    Eight,
+}
+
+impl Connectivity {
+    const FOUR: &'static [(isize, isize)] = &[(0, -1), (-1, 0), (1, 0), (0, 1)];
+    const EIGHT: &'static [(isize, isize)] = &[
+        (-1, -1),
+        (0, -1),
+        (1, -1),
+        (-1, 0),
+        (1, 0),
+        (-1, 1),
+        (0, 1),
+        (1, 1),
+    ];
+
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => Self::FOUR,
+            Connectivity::Eight => Self::EIGHT,
+        }
+    }
+}
+
+/// A dense, fixed size 2D grid stored row by row.
+///
+/// Cells are addressed by `(x, y)`, where `x` is the column and `y` the row, both starting at the
+/// top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Builds a grid with every cell set to `value`.
+    ///
+    /// # Panics
+    ///
+    /// If `width` is zero.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        assert!(width > 0, "A grid needs a width of at least 1");
+
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from cells listed row by row.
+    ///
+    /// # Panics
+    ///
+    /// If the number of cells is not a multiple of `width`.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't form rows of width {width}",
+            cells.len()
+        );
+
+        Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Parses a character map, one row per line, converting every character with `cell`.
+    pub fn parse_with(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, InputError>,
+    ) -> Result<Self, InputError> {
+        let mut width = None;
+        let mut cells = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let located = |error: InputError| error.at_line(index + 1, line);
+
+            let row_width = line.chars().count();
+            if *width.get_or_insert(row_width) != row_width {
+                let reason = format!("Expected a row of width {}", width.unwrap_or_default());
+                return Err(located(InputError::invalid(line, reason)));
+            }
+
+            for (offset, symbol) in line.char_indices() {
+                let value = cell(symbol).map_err(|error| {
+                    located(error.within(line, &line[offset..offset + symbol.len_utf8()]))
+                })?;
+                cells.push(value);
+            }
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self::from_cells(width, cells)),
+            _ => Err(InputError::invalid(input, "Expected a non-empty grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.offset(position)])
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        if !self.contains(position) {
+            return None;
+        }
+        let offset = self.offset(position);
+
+        Some(&mut self.cells[offset])
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every cell together with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(offset, cell)| ((offset % width, offset / width), cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Cells of the rectangle spanned by two corners, both inclusive, row by row.
+    ///
+    /// # Panics
+    ///
+    /// If the rectangle reaches outside of the grid.
+    pub fn rectangle(
+        &self,
+        (x1, y1): (usize, usize),
+        (x2, y2): (usize, usize),
+    ) -> impl Iterator<Item = &T> {
+        let (columns, rows) = (x1.min(x2)..=x1.max(x2), y1.min(y2)..=y1.max(y2));
+        assert!(
+            self.contains((*columns.end(), *rows.end())),
+            "{:?} is outside of the grid",
+            (*columns.end(), *rows.end())
+        );
+
+        self.cells
+            .chunks(self.width)
+            .skip(*rows.start())
+            .take(rows.count())
+            .flat_map(move |row| &row[columns.clone()])
+    }
+
+    /// Mutable version of [`Grid::rectangle`].
+    ///
+    /// # Panics
+    ///
+    /// If the rectangle reaches outside of the grid.
+    pub fn rectangle_mut(
+        &mut self,
+        (x1, y1): (usize, usize),
+        (x2, y2): (usize, usize),
+    ) -> impl Iterator<Item = &mut T> {
+        let (columns, rows) = (x1.min(x2)..=x1.max(x2), y1.min(y2)..=y1.max(y2));
+        assert!(
+            self.contains((*columns.end(), *rows.end())),
+            "{:?} is outside of the grid",
+            (*columns.end(), *rows.end())
+        );
+
+        self.cells
+            .chunks_mut(self.width)
+            .skip(*rows.start())
+            .take(rows.count())
+            .flat_map(move |row| &mut row[columns.clone()])
+    }
+
+    /// Positions of the neighbors of a cell that are inside of the grid.
+    pub fn neighbors(
+        &self,
+        (x, y): (usize, usize),
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        connectivity.offsets().iter().filter_map(move |&(dx, dy)| {
+            let neighbor = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+
+            self.contains(neighbor).then_some(neighbor)
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid with one character per cell and a line per row.
+    pub fn render(&self, mut symbol: impl FnMut(&T) -> char) -> String {
+        let mut rendered = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            rendered.extend(row.iter().map(&mut symbol));
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+
+    fn offset(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &Self::Output {
+        assert!(
+            self.contains(position),
+            "{position:?} is outside of the grid"
+        );
+
+        &self.cells[self.offset(position)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut Self::Output {
+        assert!(
+            self.contains(position),
+            "{position:?} is outside of the grid"
+        );
+        let offset = self.offset(position);
+
+        &mut self.cells[offset]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = InputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Ok)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..\n.#.\n..#\n.##\n";
+
+    #[test]
+    fn parses_and_prints_character_maps() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(4, grid.height());
+        assert_eq!('#', grid[(1, 3)]);
+        assert_eq!(MAP, grid.to_string());
+    }
+
+    #[test]
+    fn reports_ragged_rows() {
+        let error = "#..\n.#\n".parse::<Grid<char>>().unwrap_err();
+
+        assert_eq!(
+            "input:2:1: Expected a row of width 3: '.#'",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn iterates_rows_columns_and_rectangles() {
+        let grid = Grid::from_cells(3, (0..12).collect());
+
+        assert_eq!(&[3, 4, 5], grid.row(1));
+        assert_eq!(vec![&1, &4, &7, &10], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(
+            vec![&4, &5, &7, &8],
+            grid.rectangle((2, 2), (1, 1)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 1) is outside of the grid")]
+    fn rejects_rectangles_reaching_outside_of_the_grid() {
+        let mut grid = Grid::new(3, 4, 0);
+
+        grid.rectangle_mut((0, 0), (3, 1))
+            .for_each(|cell| *cell = 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "A grid needs a width of at least 1")]
+    fn rejects_grids_without_columns() {
+        Grid::new(0, 4, 0);
+    }
+
+    #[test]
+    fn finds_neighbors_inside_of_the_grid() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(2, grid.neighbors((0, 0), Connectivity::Four).count());
+        assert_eq!(3, grid.neighbors((0, 0), Connectivity::Eight).count());
+        assert_eq!(8, grid.neighbors((1, 1), Connectivity::Eight).count());
+    }
+
+    #[test]
+    fn renders_with_custom_symbols() {
+        let mut grid = Grid::new(2, 2, false);
+        grid.rectangle_mut((1, 0), (1, 1))
+            .for_each(|cell| *cell = true);
+
+        assert_eq!(".#\n.#\n", grid.render(|&on| if on { '#' } else { '.' }));
+    }
+}
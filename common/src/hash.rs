@@ -0,0 +1,416 @@
+//! Searching for numbers whose md5 hash, appended to a key, matches a predicate.
+
+pub use md5::Digest;
+
+use crate::anyhow::{anyhow, bail};
+use crate::Result;
+
+use regex::Regex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+
+/// Numbers every thread checks per batch of a [`NonceSearch`].
+const CHUNK: u64 = 10_000;
+
+/// ASCII decimal digits of a number that is incremented in place instead of formatted again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitCounter {
+    value: u64,
+    digits: Vec<u8>,
+}
+
+impl DigitCounter {
+    pub fn new(start: u64) -> Self {
+        Self {
+            value: start,
+            digits: start.to_string().into_bytes(),
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn digits(&self) -> &[u8] {
+        &self.digits
+    }
+
+    pub fn increment(&mut self) {
+        self.value += 1;
+        for digit in self.digits.iter_mut().rev() {
+            if *digit == b'9' {
+                *digit = b'0';
+            } else {
+                *digit += 1;
+                return;
+            }
+        }
+
+        self.digits.insert(0, b'1');
+    }
+}
+
+/// The md5 state after consuming a key, reused for every suffix hashed after it.
+#[derive(Clone)]
+pub struct KeyedMd5 {
+    prefix: md5::Context,
+}
+
+impl KeyedMd5 {
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        let mut prefix = md5::Context::new();
+        prefix.consume(key);
+
+        Self { prefix }
+    }
+
+    /// The digest of the key followed by `suffix`.
+    pub fn digest(&self, suffix: impl AsRef<[u8]>) -> Digest {
+        let mut context = self.prefix.clone();
+        context.consume(suffix);
+
+        context.compute()
+    }
+}
+
+/// A condition a digest has to meet, shared by the threads of a search.
+pub trait DigestPredicate: Sync {
+    fn matches(&self, digest: &Digest) -> bool;
+}
+
+impl<F: Fn(&Digest) -> bool + Sync> DigestPredicate for F {
+    fn matches(&self, digest: &Digest) -> bool {
+        self(digest)
+    }
+}
+
+/// The digest starts with at least this many zero bits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LeadingZeroBits(pub u32);
+
+impl DigestPredicate for LeadingZeroBits {
+    fn matches(&self, digest: &Digest) -> bool {
+        u128::from_be_bytes(digest.0).leading_zeros() >= self.0
+    }
+}
+
+/// The hex form of the digest starts with these digits, compared without formatting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexPrefix {
+    nibbles: Vec<u8>,
+}
+
+impl HexPrefix {
+    pub fn new(prefix: &str) -> Result<Self> {
+        if prefix.len() > 32 {
+            bail!("An md5 digest has only 32 hex digits, got '{prefix}'");
+        }
+        let nibbles = prefix
+            .chars()
+            .map(|digit| {
+                let nibble = digit
+                    .to_digit(16)
+                    .ok_or_else(|| anyhow!("'{digit}' is not a hex digit"))?;
+
+                Ok(nibble as u8)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { nibbles })
+    }
+
+    /// A prefix of `count` zeroes.
+    pub fn zeros(count: usize) -> Self {
+        Self {
+            nibbles: vec![0; count.min(32)],
+        }
+    }
+}
+
+impl DigestPredicate for HexPrefix {
+    fn matches(&self, digest: &Digest) -> bool {
+        self.nibbles.iter().enumerate().all(|(index, &nibble)| {
+            let byte = digest.0[index / 2];
+            let actual = if index % 2 == 0 {
+                byte >> 4
+            } else {
+                byte & 0x0f
+            };
+
+            actual == nibble
+        })
+    }
+}
+
+/// The lowercase hex form of the digest matches a regular expression.
+#[derive(Debug, Clone)]
+pub struct HexRegex(Regex);
+
+impl HexRegex {
+    pub fn new(pattern: &str) -> Result<Self> {
+        Ok(Self(Regex::new(pattern)?))
+    }
+}
+
+impl DigestPredicate for HexRegex {
+    fn matches(&self, digest: &Digest) -> bool {
+        self.0.is_match(&format!("{digest:x}"))
+    }
+}
+
+/// The digest starts with these bytes, where `None` matches any byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BytePattern(pub Vec<Option<u8>>);
+
+impl DigestPredicate for BytePattern {
+    fn matches(&self, digest: &Digest) -> bool {
+        self.0.len() <= digest.0.len()
+            && self
+                .0
+                .iter()
+                .zip(digest.0)
+                .all(|(expected, byte)| expected.is_none_or(|expected| expected == byte))
+    }
+}
+
+/// Every number whose md5 hash, appended to a key in decimal, matches a predicate, in order.
+///
+/// Numbers are checked in batches, split between all cores. The iterator only ends once
+/// cancelled. For only the first match, [`find_nonce`] stops sooner.
+pub struct NonceSearch<'a, P> {
+    hasher: KeyedMd5,
+    predicate: P,
+    next: u64,
+    found: VecDeque<u64>,
+    cancel: Option<&'a AtomicBool>,
+    threads: u64,
+}
+
+impl<'a, P: DigestPredicate> NonceSearch<'a, P> {
+    pub fn new(key: impl AsRef<[u8]>, predicate: P) -> Self {
+        Self {
+            hasher: KeyedMd5::new(key),
+            predicate,
+            next: 0,
+            found: VecDeque::new(),
+            cancel: None,
+            threads: threads(),
+        }
+    }
+
+    pub fn starting_at(mut self, start: u64) -> Self {
+        self.next = start;
+        self
+    }
+
+    /// Stops the search once `cancel` is set, which the threads of a running batch check before
+    /// every number.
+    pub fn cancelled_by(mut self, cancel: &'a AtomicBool) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+
+    fn search_batch(&mut self) {
+        let (hasher, predicate, cancel) = (&self.hasher, &self.predicate, self.cancel);
+        let is_cancelled = move || cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed));
+        let starts = (0..self.threads).map(|thread| self.next + thread * CHUNK);
+
+        let found: Vec<Vec<u64>> = thread::scope(|scope| {
+            let searches: Vec<_> = starts
+                .map(|start| {
+                    scope.spawn(move || {
+                        let mut found = Vec::new();
+                        let mut counter = DigitCounter::new(start);
+                        while counter.value() < start + CHUNK && !is_cancelled() {
+                            if predicate.matches(&hasher.digest(counter.digits())) {
+                                found.push(counter.value());
+                            }
+                            counter.increment();
+                        }
+
+                        found
+                    })
+                })
+                .collect();
+
+            searches
+                .into_iter()
+                .map(|search| search.join().expect("Search thread panicked"))
+                .collect()
+        });
+
+        // The chunks are consecutive, so their matches are already in order.
+        self.found.extend(found.into_iter().flatten());
+        self.next += self.threads * CHUNK;
+    }
+}
+
+impl<P: DigestPredicate> Iterator for NonceSearch<'_, P> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.is_cancelled() {
+                return None;
+            }
+            if let Some(found) = self.found.pop_front() {
+                return Some(found);
+            }
+
+            self.search_batch();
+        }
+    }
+}
+
+/// The lowest number whose md5 hash, appended to `key`, matches `predicate`, unless cancelled.
+///
+/// Threads take chunks of numbers in increasing order and stop once a match was found below the
+/// next chunk, so the result doesn't depend on scheduling. `cancel` is checked before every
+/// number.
+pub fn find_nonce(key: &str, predicate: impl DigestPredicate, cancel: &AtomicBool) -> Option<u64> {
+    let (hasher, predicate) = (KeyedMd5::new(key), &predicate);
+    let next_chunk = AtomicU64::new(0);
+    let lowest = AtomicU64::new(u64::MAX);
+
+    thread::scope(|scope| {
+        for _ in 0..threads() {
+            scope.spawn(|| loop {
+                let start = next_chunk.fetch_add(CHUNK, Ordering::Relaxed);
+                if start >= lowest.load(Ordering::Relaxed) || cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let mut counter = DigitCounter::new(start);
+                while counter.value() < start + CHUNK && !cancel.load(Ordering::Relaxed) {
+                    if predicate.matches(&hasher.digest(counter.digits())) {
+                        lowest.fetch_min(counter.value(), Ordering::Relaxed);
+                        break;
+                    }
+                    counter.increment();
+                }
+            });
+        }
+    });
+
+    let lowest = lowest.into_inner();
+
+    (lowest != u64::MAX && !cancel.load(Ordering::Relaxed)).then_some(lowest)
+}
+
+fn threads() -> u64 {
+    thread::available_parallelism().map_or(1, |threads| threads.get()) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_in_decimal_digits() {
+        let mut counter = DigitCounter::new(98);
+        counter.increment();
+        assert_eq!(b"99", counter.digits());
+        counter.increment();
+        assert_eq!(b"100", counter.digits());
+        assert_eq!(100, counter.value());
+    }
+
+    #[test]
+    fn reuses_the_key_state() {
+        let hasher = KeyedMd5::new("abcdef");
+
+        assert_eq!(md5::compute("abcdef609043"), hasher.digest("609043"));
+    }
+
+    #[test]
+    fn matches_predicates() {
+        // The hash of abcdef609043 starts with 000001dbbfa.
+        let digest = md5::compute("abcdef609043");
+
+        assert!(HexPrefix::zeros(5).matches(&digest));
+        assert!(HexPrefix::new("000001DB").unwrap().matches(&digest));
+        assert!(!HexPrefix::zeros(6).matches(&digest));
+        assert!(HexPrefix::new("00x").is_err());
+
+        assert!(LeadingZeroBits(23).matches(&digest));
+        assert!(!LeadingZeroBits(24).matches(&digest));
+
+        assert!(HexRegex::new("^0{5}1d").unwrap().matches(&digest));
+        assert!(BytePattern(vec![Some(0), None, Some(0x01)]).matches(&digest));
+        assert!(!BytePattern(vec![Some(0), Some(0), Some(0)]).matches(&digest));
+    }
+
+    #[test]
+    fn finds_the_lowest_number_on_every_run() {
+        let cancel = AtomicBool::new(false);
+
+        for _ in 0..3 {
+            assert_eq!(
+                Some(298),
+                find_nonce("abcdef", HexPrefix::zeros(2), &cancel)
+            );
+        }
+    }
+
+    #[test]
+    fn iterates_all_matches_in_order() {
+        let all: Vec<_> = NonceSearch::new("abcdef", HexPrefix::zeros(2))
+            .take(20)
+            .collect();
+        let expected: Vec<_> = (0..)
+            .filter(|number| {
+                format!("{:x}", md5::compute(format!("abcdef{number}"))).starts_with("00")
+            })
+            .take(20)
+            .collect();
+
+        assert_eq!(expected, all);
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let found = find_nonce("abcdef", |_: &Digest| false, &AtomicBool::new(true));
+
+        assert_eq!(None, found);
+    }
+
+    #[test]
+    fn stops_iterating_within_a_batch_when_cancelled() {
+        let cancel = AtomicBool::new(false);
+        let checked = AtomicU64::new(0);
+        let predicate = |_: &Digest| {
+            checked.fetch_add(1, Ordering::Relaxed);
+            cancel.store(true, Ordering::Relaxed);
+            false
+        };
+
+        assert_eq!(
+            None,
+            NonceSearch::new("abcdef", predicate)
+                .cancelled_by(&cancel)
+                .next()
+        );
+        // Each thread may check one more number that it started before the flag was set.
+        assert!(checked.into_inner() <= 2 * threads());
+    }
+
+    #[test]
+    fn stops_iterating_within_a_chunk_when_cancelled() {
+        let cancel = AtomicBool::new(false);
+        let checked = AtomicU64::new(0);
+        let predicate = |_: &Digest| {
+            checked.fetch_add(1, Ordering::Relaxed);
+            cancel.store(true, Ordering::Relaxed);
+            false
+        };
+
+        assert_eq!(None, find_nonce("abcdef", predicate, &cancel));
+        assert!(checked.into_inner() <= 2 * threads());
+    }
+}
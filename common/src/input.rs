@@ -0,0 +1,193 @@
+use crate::{solver, with_input_file, Answers, Config, Day, InputError, Result, Year};
+
+use anyhow::{anyhow, Context};
+use std::fmt::{self, Display};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+/// Where a candidate input directory came from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Flag,
+    Environment,
+    Config,
+    Workspace,
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            InputSource::Flag => "--input-dir",
+            InputSource::Environment => InputLocator::DIR_VARIABLE,
+            InputSource::Config => Config::FILE_NAME,
+            InputSource::Workspace => "workspace root",
+        };
+
+        f.write_str(source)
+    }
+}
+
+/// Finds puzzle inputs stored as `<input dir>/<year>/day<NN>`.
+///
+/// Candidate directories are tried in order: the `--input-dir` flag, `$AOC_INPUT_DIR`,
+/// `input_dir` from the config file and finally `input/` in the workspace root.
+#[derive(Debug, Default, Clone)]
+pub struct InputLocator {
+    dirs: Vec<(InputSource, PathBuf)>,
+}
+
+impl InputLocator {
+    pub const DIR_VARIABLE: &'static str = "AOC_INPUT_DIR";
+
+    pub fn discover(flag: Option<&Path>, config: &Config) -> Self {
+        let mut locator = Self::default();
+
+        if let Some(dir) = flag {
+            locator = locator.with_dir(InputSource::Flag, dir);
+        }
+        if let Some(dir) = std::env::var_os(Self::DIR_VARIABLE) {
+            locator = locator.with_dir(InputSource::Environment, dir);
+        }
+        if let Some(dir) = &config.input_dir {
+            locator = locator.with_dir(InputSource::Config, dir);
+        }
+        for root in workspace_roots() {
+            locator = locator.with_dir(InputSource::Workspace, root.join("input"));
+        }
+
+        locator
+    }
+
+    pub fn with_dir(mut self, source: InputSource, dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        if !self.dirs.iter().any(|(_, known)| *known == dir) {
+            self.dirs.push((source, dir));
+        }
+
+        self
+    }
+
+    /// The directory new inputs should be stored in: the first candidate.
+    pub fn primary_dir(&self) -> Option<&Path> {
+        self.dirs.first().map(|(_, dir)| dir.as_path())
+    }
+
+    pub fn locate(&self, year: Year, day: Day) -> Result<PathBuf, InputError> {
+        let candidates: Vec<_> = self
+            .dirs
+            .iter()
+            .map(|(source, dir)| (*source, input_path(dir, year, day)))
+            .collect();
+
+        if let Some((_, path)) = candidates.iter().find(|(_, path)| path.is_file()) {
+            return Ok(path.clone());
+        }
+
+        Err(InputError::NotFound {
+            year,
+            day,
+            tried: candidates,
+        })
+    }
+
+    pub fn read(&self, year: Year, day: Day) -> Result<String> {
+        read_file(&self.locate(year, day)?)
+    }
+
+    /// Runs the registered solution of the day on its input.
+    ///
+    /// Input errors of the solution are reported with the path of the input file.
+    pub fn solve(&self, year: Year, day: Day) -> Result<Answers> {
+        let solver = solver(year, day).ok_or_else(|| anyhow!("{year} day {day} is not solved"))?;
+        let path = self.locate(year, day)?;
+        let input = read_file(&path)?;
+
+        solver(&input).map_err(|error| with_input_file(error, path))
+    }
+}
+
+/// Reads an input using the default lookup, without a `--input-dir` flag.
+pub fn read_input(year: Year, day: Day) -> Result<String> {
+    let config = Config::load()?;
+
+    InputLocator::discover(None, &config).read(year, day)
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+pub fn input_path(dir: &Path, year: Year, day: Day) -> PathBuf {
+    dir.join(year.to_string()).join(format!("day{day}"))
+}
+
+/// The workspace root containing the current directory, or the one this crate was built in.
+pub fn workspace_root() -> Option<PathBuf> {
+    workspace_roots().into_iter().next()
+}
+
+/// The workspace root containing the current directory, plus the one this crate was built in.
+fn workspace_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+
+    if let Ok(current_dir) = std::env::current_dir() {
+        if let Some(root) = current_dir.ancestors().find(|dir| is_workspace_root(dir)) {
+            roots.push(root.to_path_buf());
+        }
+    }
+    if let Some(root) = Path::new(env!("CARGO_MANIFEST_DIR")).parent() {
+        roots.push(root.to_path_buf());
+    }
+
+    roots
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+    read_to_string(dir.join("Cargo.toml")).is_ok_and(|manifest| manifest.contains("[workspace]"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_server::TempDir;
+    use std::fs;
+
+    #[test]
+    fn picks_first_directory_with_the_input() {
+        let empty = TempDir::new("input-empty");
+        let filled = TempDir::new("input-filled");
+        fs::create_dir_all(filled.join("2015")).unwrap();
+        fs::write(filled.join("2015/day01"), "(()").unwrap();
+
+        let locator = InputLocator::default()
+            .with_dir(InputSource::Flag, empty.path())
+            .with_dir(InputSource::Config, filled.path());
+
+        assert_eq!(
+            filled.join("2015/day01"),
+            locator.locate(Year(2015), Day(1)).unwrap()
+        );
+        assert_eq!("(()", locator.read(Year(2015), Day(1)).unwrap());
+    }
+
+    #[test]
+    fn reports_every_path_tried() {
+        let first = TempDir::new("input-first");
+        let second = TempDir::new("input-second");
+
+        let locator = InputLocator::default()
+            .with_dir(InputSource::Environment, first.path())
+            .with_dir(InputSource::Workspace, second.path());
+        let error = locator.locate(Year(2015), Day(7)).unwrap_err().to_string();
+
+        assert!(error.contains(&format!(
+            "{} (from AOC_INPUT_DIR)",
+            first.join("2015/day07").display()
+        )));
+        assert!(error.contains(&format!(
+            "{} (from workspace root)",
+            second.join("2015/day07").display()
+        )));
+    }
+}
@@ -1,28 +1,130 @@
 pub use anyhow::{self, Error, Result};
+pub use inventory;
 
-use std::fs::{read_to_string, File};
-use std::io::{BufRead, BufReader, Lines};
+mod answers;
+mod bench;
+mod bitgrid;
+mod client;
+mod config;
+mod error;
+mod examples;
+mod fetch;
+pub mod geometry;
+mod grid;
+pub mod hash;
+mod input;
+mod regions;
+mod registry;
+mod solution;
+mod submit;
+#[cfg(test)]
+mod test_server;
+mod timing;
+mod verify;
+pub mod viz;
 
-pub type InputLines = Lines<BufReader<File>>;
+pub use answers::KnownAnswers;
+pub use bench::{benchmark, BenchHistory, BenchRun, DayBench};
+pub use bitgrid::BitGrid;
+pub use client::Client;
+pub use config::Config;
+pub use error::{column_of, parse_lines, with_input_file, InputError, Location};
+pub use fetch::{fetch_input, unlock_time, Fetched};
+pub use grid::{Connectivity, Grid};
+pub use input::{input_path, read_input, workspace_root, InputLocator, InputSource};
+pub use regions::Regions;
+pub use registry::{
+    missing_days, registered_days, registered_years, registrations, solver, Registration,
+};
+pub use solution::{solve, Answers, Solution, Solver, Timings};
+pub use submit::{submit_answer, Bounds, Submission, SubmitOutcome, Verdict, Verdicts};
+pub use timing::{format_duration, timed, Stats};
+pub use verify::{verify, verify_year, Check, VerifyReport};
 
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Day(pub u8);
 
+impl Day {
+    pub const LAST: Day = Day(25);
+
+    pub fn all() -> impl Iterator<Item = Day> {
+        (1..=Self::LAST.0).map(Day)
+    }
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.0)
+    }
+}
+
+impl FromStr for Day {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let day = s.parse()?;
+        if !(1..=Self::LAST.0).contains(&day) {
+            anyhow::bail!("Day must be between 1 and {}, got {day}", Self::LAST.0);
+        }
+
+        Ok(Day(day))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Year(pub u32);
 
-pub fn read_input_lines(year: Year, day: Day) -> Result<InputLines> {
-    let path = input_path(year, day);
-    let file = File::open(path)?;
+impl Display for Year {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
-    Ok(BufReader::new(file).lines())
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Part {
+    One = 1,
+    Two = 2,
 }
 
-pub fn read_single_input_line(year: Year, day: Day) -> Result<String> {
-    let path = input_path(year, day);
-    let input = read_to_string(path)?;
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.write_str("one"),
+            Part::Two => f.write_str("two"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
 
-    Ok(input)
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => anyhow::bail!("Expected part 1 or 2, got '{s}'"),
+        }
+    }
 }
 
-fn input_path(year: Year, day: Day) -> String {
-    format!("input/{}/day{:02}", year.0, day.0)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_days_of_the_event_only() {
+        assert_eq!(Day(7), "7".parse().unwrap());
+        assert_eq!(Day(25), "25".parse().unwrap());
+        assert_eq!(
+            "Day must be between 1 and 25, got 0",
+            "0".parse::<Day>().unwrap_err().to_string()
+        );
+        assert!("26".parse::<Day>().is_err());
+        assert!("x".parse::<Day>().is_err());
+    }
 }
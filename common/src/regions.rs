@@ -0,0 +1,203 @@
+use crate::geometry::{Interval, Point, Rect};
+use crate::Grid;
+
+use std::iter;
+use std::ops::Range;
+
+/// Values over a bounded area, stored per block of a coordinate compressed grid.
+///
+/// The area is cut into blocks along the edges of the rectangles that will be updated, so that
+/// blocks are always updated as a whole. An update then touches one value per block instead of
+/// one per cell. Edges that weren't known upfront split the blocks they cross when they show up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions<T> {
+    bounds: Rect,
+    /// Where the blocks start on each axis, followed by the end of the bounds, exclusive.
+    columns: Vec<i64>,
+    rows: Vec<i64>,
+    blocks: Grid<T>,
+}
+
+impl<T: Clone> Regions<T> {
+    /// Cuts `bounds` along the edges of `rects`, filling every block with `value`.
+    pub fn new(bounds: Rect, rects: impl IntoIterator<Item = Rect>, value: T) -> Self {
+        let rects: Vec<_> = rects
+            .into_iter()
+            .filter_map(|rect| rect.intersection(&bounds))
+            .collect();
+        let columns = boundaries(bounds.columns(), rects.iter().map(Rect::columns));
+        let rows = boundaries(bounds.rows(), rects.iter().map(Rect::rows));
+        let blocks = Grid::new(columns.len() - 1, rows.len() - 1, value);
+
+        Self {
+            bounds,
+            columns,
+            rows,
+            blocks,
+        }
+    }
+
+    /// Calls `update` once for every block of `rect`, ignoring the parts outside of the bounds.
+    ///
+    /// Blocks crossed by an edge of `rect` that wasn't passed to [`Regions::new`] are split first.
+    pub fn update(&mut self, rect: Rect, update: impl FnMut(&mut T)) {
+        let Some(rect) = rect.intersection(&self.bounds) else {
+            return;
+        };
+        for edge in [rect.min.x, rect.max.x + 1] {
+            self.split_columns(edge);
+        }
+        for edge in [rect.min.y, rect.max.y + 1] {
+            self.split_rows(edge);
+        }
+        let columns = span(&self.columns, rect.columns());
+        let rows = span(&self.rows, rect.rows());
+
+        self.blocks
+            .rectangle_mut((columns.start, rows.start), (columns.end - 1, rows.end - 1))
+            .for_each(update);
+    }
+
+    /// Makes a column of blocks start at `x`, copying the values of the blocks it splits.
+    fn split_columns(&mut self, x: i64) {
+        let Err(index) = self.columns.binary_search(&x) else {
+            return;
+        };
+        self.columns.insert(index, x);
+
+        let cells = self
+            .blocks
+            .rows()
+            .flat_map(|row| row[..index].iter().chain(&row[index - 1..]))
+            .cloned()
+            .collect();
+        self.blocks = Grid::from_cells(self.blocks.width() + 1, cells);
+    }
+
+    /// Makes a row of blocks start at `y`, copying the values of the blocks it splits.
+    fn split_rows(&mut self, y: i64) {
+        let Err(index) = self.rows.binary_search(&y) else {
+            return;
+        };
+        self.rows.insert(index, y);
+
+        let cells = self
+            .blocks
+            .rows()
+            .enumerate()
+            .flat_map(|(row, cells)| iter::repeat_n(cells, if row == index - 1 { 2 } else { 1 }))
+            .flatten()
+            .cloned()
+            .collect();
+        self.blocks = Grid::from_cells(self.blocks.width(), cells);
+    }
+}
+
+impl<T> Regions<T> {
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub fn block_count(&self) -> usize {
+        self.blocks.width() * self.blocks.height()
+    }
+
+    /// The value at a single point, if it is inside of the bounds.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if !self.bounds.contains(point) {
+            return None;
+        }
+        let block =
+            |starts: &[i64], value: i64| starts.partition_point(|&start| start <= value) - 1;
+
+        self.blocks
+            .get((block(&self.columns, point.x), block(&self.rows, point.y)))
+    }
+
+    /// Every block together with the area it covers.
+    pub fn blocks(&self) -> impl Iterator<Item = (Rect, &T)> {
+        self.blocks.iter().map(|((x, y), value)| {
+            let columns = Interval::new(self.columns[x], self.columns[x + 1] - 1);
+            let rows = Interval::new(self.rows[y], self.rows[y + 1] - 1);
+
+            (Rect::from_spans(columns, rows), value)
+        })
+    }
+
+    /// Sums `weight` over every cell, evaluating it once per block.
+    pub fn sum(&self, mut weight: impl FnMut(&T) -> u64) -> u64 {
+        self.blocks()
+            .map(|(rect, value)| rect.area() * weight(value))
+            .sum()
+    }
+
+    /// Number of cells whose value matches `predicate`.
+    pub fn count(&self, mut predicate: impl FnMut(&T) -> bool) -> u64 {
+        self.sum(|value| u64::from(predicate(value)))
+    }
+}
+
+/// Sorted starts of the blocks within `bounds`, followed by its exclusive end.
+fn boundaries(bounds: Interval, spans: impl Iterator<Item = Interval>) -> Vec<i64> {
+    let mut boundaries: Vec<_> = spans
+        .flat_map(|span| [span.start, span.end + 1])
+        .chain([bounds.start, bounds.end + 1])
+        .collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    boundaries
+}
+
+/// Indices of the blocks making up `span`, whose edges are block boundaries.
+fn span(boundaries: &[i64], span: Interval) -> Range<usize> {
+    let index = |value| {
+        boundaries
+            .binary_search(&value)
+            .unwrap_or_else(|_| unreachable!("{value} is not a block boundary"))
+    };
+
+    index(span.start)..index(span.end + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_whole_blocks() {
+        let bounds = Rect::new(Point::new(0, 0), Point::new(9, 9));
+        let first = Rect::new(Point::new(2, 2), Point::new(5, 5));
+        let second = Rect::new(Point::new(4, 0), Point::new(12, 3));
+
+        let mut regions = Regions::new(bounds, [first, second], 0);
+        regions.update(first, |value| *value += 1);
+        regions.update(second, |value| *value += 2);
+
+        assert_eq!(16, regions.block_count());
+        assert_eq!(16 + 24 * 2, regions.sum(|&value| value));
+        assert_eq!(4, regions.count(|&value| value == 3));
+        assert_eq!(Some(&3), regions.get(Point::new(5, 3)));
+        assert_eq!(Some(&0), regions.get(Point::new(9, 9)));
+        assert_eq!(None, regions.get(Point::new(10, 0)));
+    }
+
+    #[test]
+    fn splits_blocks_at_unknown_edges() {
+        let bounds = Rect::new(Point::new(0, 0), Point::new(9, 9));
+        let first = Rect::new(Point::new(2, 2), Point::new(5, 5));
+        let mut regions = Regions::new(bounds, [first], 0);
+        regions.update(first, |value| *value += 1);
+
+        regions.update(Rect::new(Point::new(4, 1), Point::new(12, 3)), |value| {
+            *value += 2
+        });
+
+        assert_eq!(5 * 4, regions.block_count());
+        assert_eq!(16 + 18 * 2, regions.sum(|&value| value));
+        assert_eq!(4, regions.count(|&value| value == 3));
+        assert_eq!(Some(&1), regions.get(Point::new(3, 3)));
+        assert_eq!(Some(&0), regions.get(Point::new(3, 1)));
+        assert_eq!(Some(&2), regions.get(Point::new(9, 1)));
+    }
+}
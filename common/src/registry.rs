@@ -0,0 +1,65 @@
+use crate::{Day, Solver, Year};
+
+/// A solved day, submitted with [`register!`](crate::register) from the module that solves it.
+#[derive(Debug)]
+pub struct Registration {
+    pub year: Year,
+    pub day: Day,
+    pub solver: Solver,
+}
+
+inventory::collect!(Registration);
+
+/// Registers a [`Solution`](crate::Solution) for the given year and day.
+///
+/// ```ignore
+/// common::register!(2015, 1, Day01);
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($year:expr, $day:expr, $solution:ty) => {
+        $crate::inventory::submit! {
+            $crate::Registration {
+                year: $crate::Year($year),
+                day: $crate::Day($day),
+                solver: $crate::solve::<$solution>,
+            }
+        }
+    };
+}
+
+pub fn registrations() -> impl Iterator<Item = &'static Registration> {
+    inventory::iter::<Registration>.into_iter()
+}
+
+pub fn solver(year: Year, day: Day) -> Option<Solver> {
+    registrations()
+        .find(|registration| registration.year == year && registration.day == day)
+        .map(|registration| registration.solver)
+}
+
+pub fn registered_years() -> Vec<Year> {
+    let mut years: Vec<_> = registrations()
+        .map(|registration| registration.year)
+        .collect();
+    years.sort_unstable();
+    years.dedup();
+
+    years
+}
+
+pub fn registered_days(year: Year) -> Vec<Day> {
+    let mut days: Vec<_> = registrations()
+        .filter(|registration| registration.year == year)
+        .map(|registration| registration.day)
+        .collect();
+    days.sort_unstable();
+
+    days
+}
+
+pub fn missing_days(year: Year) -> Vec<Day> {
+    let registered = registered_days(year);
+
+    Day::all().filter(|day| !registered.contains(day)).collect()
+}
@@ -0,0 +1,63 @@
+use crate::{timed, Result};
+
+use std::fmt::Display;
+use std::time::Duration;
+
+/// A solver for a single day of Advent of Code.
+///
+/// The input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne>;
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo>;
+}
+
+/// Type-erased entry point of a [`Solution`], used by the runner to dispatch by year and day.
+pub type Solver = fn(&str) -> Result<Answers>;
+
+/// Answers of both parts rendered as strings.
+///
+/// A part that failed keeps its error so the other part can still be reported.
+#[derive(Debug)]
+pub struct Answers {
+    pub part_one: Result<String>,
+    pub part_two: Result<String>,
+    pub timings: Timings,
+}
+
+/// How long parsing and each of the parts took.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_one: Duration,
+    pub part_two: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one + self.part_two
+    }
+}
+
+pub fn solve<S: Solution>(input: &str) -> Result<Answers> {
+    let (input, parse) = timed(|| S::parse(input));
+    let input = input?;
+    let (part_one, part_one_time) = timed(|| S::part_one(&input).map(|answer| answer.to_string()));
+    let (part_two, part_two_time) = timed(|| S::part_two(&input).map(|answer| answer.to_string()));
+
+    Ok(Answers {
+        part_one,
+        part_two,
+        timings: Timings {
+            parse,
+            part_one: part_one_time,
+            part_two: part_two_time,
+        },
+    })
+}
@@ -0,0 +1,322 @@
+use crate::{Client, Day, Part, Result, Year};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How the server responded to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The answer was not checked because the previous submission was too recent.
+    #[serde(skip)]
+    Wait(Option<Duration>),
+    /// The part is already solved or not unlocked yet, so the answer was not checked.
+    #[serde(skip)]
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Whether the answer itself was judged, as opposed to the submission being rejected.
+    pub fn is_judged(&self) -> bool {
+        !matches!(self, Verdict::Wait(_) | Verdict::WrongLevel)
+    }
+
+    pub fn parse(response: &str) -> Option<Self> {
+        let verdict = if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if response.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::Wait(parse_wait(response))
+        } else if response.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            return None;
+        };
+
+        Some(verdict)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("correct"),
+            Verdict::Incorrect => f.write_str("incorrect"),
+            Verdict::TooHigh => f.write_str("too high"),
+            Verdict::TooLow => f.write_str("too low"),
+            Verdict::Wait(Some(remaining)) => write!(f, "wait {}s", remaining.as_secs()),
+            Verdict::Wait(None) => f.write_str("wait"),
+            Verdict::WrongLevel => f.write_str("already solved or locked"),
+        }
+    }
+}
+
+/// Parses "You have 1m 5s left to wait."
+fn parse_wait(response: &str) -> Option<Duration> {
+    let start = response.find("You have ")? + "You have ".len();
+    let end = start + response[start..].find(" left to wait")?;
+
+    response[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Judged submissions of one year, stored as `<input dir>/<year>/verdicts.toml`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Verdicts {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    submissions: Vec<Submission>,
+}
+
+/// Answers known to be wrong because of earlier verdicts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    /// The highest answer that was too low.
+    pub above: Option<i64>,
+    /// The lowest answer that was too high.
+    pub below: Option<i64>,
+}
+
+impl Bounds {
+    pub fn allows(&self, answer: i64) -> bool {
+        self.above.is_none_or(|above| answer > above)
+            && self.below.is_none_or(|below| answer < below)
+    }
+}
+
+impl Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.above, self.below) {
+            (Some(above), Some(below)) => write!(f, "{above} < answer < {below}"),
+            (Some(above), None) => write!(f, "answer > {above}"),
+            (None, Some(below)) => write!(f, "answer < {below}"),
+            (None, None) => f.write_str("no known bounds"),
+        }
+    }
+}
+
+impl Verdicts {
+    pub const FILE_NAME: &'static str = "verdicts.toml";
+
+    pub fn load(input_dir: &Path, year: Year) -> Result<Self> {
+        let path = input_dir.join(year.to_string()).join(Self::FILE_NAME);
+        let mut verdicts: Self = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(error) => return Err(error.into()),
+        };
+        verdicts.path = path;
+
+        Ok(verdicts)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, toml::to_string(self)?)?;
+
+        Ok(())
+    }
+
+    pub fn submissions(&self, day: Day, part: Part) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |submission| submission.day == day.0 && submission.part == part)
+    }
+
+    pub fn accepted(&self, day: Day, part: Part) -> Option<&str> {
+        self.submissions(day, part)
+            .find(|submission| submission.verdict == Verdict::Correct)
+            .map(|submission| submission.answer.as_str())
+    }
+
+    pub fn previous(&self, day: Day, part: Part, answer: &str) -> Option<Verdict> {
+        self.submissions(day, part)
+            .find(|submission| submission.answer == answer)
+            .map(|submission| submission.verdict)
+    }
+
+    pub fn bounds(&self, day: Day, part: Part) -> Bounds {
+        self.submissions(day, part)
+            .fold(Bounds::default(), |bounds, submission| {
+                let Ok(answer) = submission.answer.parse::<i64>() else {
+                    return bounds;
+                };
+
+                match submission.verdict {
+                    Verdict::TooLow => Bounds {
+                        above: bounds.above.max(Some(answer)),
+                        ..bounds
+                    },
+                    Verdict::TooHigh => Bounds {
+                        below: Some(bounds.below.map_or(answer, |below| below.min(answer))),
+                        ..bounds
+                    },
+                    _ => bounds,
+                }
+            })
+    }
+
+    pub fn record(&mut self, day: Day, part: Part, answer: &str, verdict: Verdict) {
+        if verdict.is_judged() {
+            self.submissions.push(Submission {
+                day: day.0,
+                part,
+                answer: answer.to_string(),
+                verdict,
+            });
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    /// The server judged the answer.
+    Submitted(Verdict),
+    /// The answer was not sent, because the earlier verdicts already decide it.
+    AlreadyJudged(Verdict),
+    /// The answer was not sent, because the part is already solved with a different answer.
+    AlreadySolved(String),
+    /// The answer was not sent, because it is outside of the known bounds.
+    OutOfBounds(Bounds),
+}
+
+/// Submits an answer unless the recorded verdicts already tell how it would be judged.
+pub fn submit_answer(
+    client: &Client,
+    verdicts: &mut Verdicts,
+    year: Year,
+    day: Day,
+    part: Part,
+    answer: &str,
+) -> Result<SubmitOutcome> {
+    let answer = answer.trim();
+
+    if let Some(verdict) = verdicts.previous(day, part, answer) {
+        return Ok(SubmitOutcome::AlreadyJudged(verdict));
+    }
+    if let Some(accepted) = verdicts.accepted(day, part) {
+        return Ok(SubmitOutcome::AlreadySolved(accepted.to_string()));
+    }
+
+    let bounds = verdicts.bounds(day, part);
+    if let Ok(number) = answer.parse() {
+        if !bounds.allows(number) {
+            return Ok(SubmitOutcome::OutOfBounds(bounds));
+        }
+    }
+
+    let verdict = client.submit_answer(year, day, part, answer)?;
+    verdicts.record(day, part, answer, verdict);
+    verdicts.save()?;
+
+    Ok(SubmitOutcome::Submitted(verdict))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{TempDir, TestServer};
+
+    #[test]
+    fn parses_responses() {
+        let too_high = "<p>That's not the right answer; your answer is too high.</p>";
+        let wait = "<p>You gave an answer too recently. You have 1m 5s left to wait.</p>";
+
+        assert_eq!(
+            Some(Verdict::Correct),
+            Verdict::parse("<p>That's the right answer!</p>")
+        );
+        assert_eq!(Some(Verdict::TooHigh), Verdict::parse(too_high));
+        assert_eq!(
+            Some(Verdict::Wait(Some(Duration::from_secs(65)))),
+            Verdict::parse(wait)
+        );
+        assert_eq!(None, Verdict::parse("<html>Something else</html>"));
+    }
+
+    #[test]
+    fn tracks_bounds() {
+        let dir = TempDir::new("submit-bounds");
+        let mut verdicts = Verdicts::load(&dir, Year(2015)).unwrap();
+        verdicts.record(Day(1), Part::One, "10", Verdict::TooLow);
+        verdicts.record(Day(1), Part::One, "50", Verdict::TooHigh);
+        verdicts.record(Day(1), Part::One, "40", Verdict::TooHigh);
+        verdicts.record(Day(1), Part::Two, "5", Verdict::TooHigh);
+
+        let bounds = verdicts.bounds(Day(1), Part::One);
+
+        assert_eq!(Some(10), bounds.above);
+        assert_eq!(Some(40), bounds.below);
+        assert!(bounds.allows(20));
+        assert!(!bounds.allows(45));
+    }
+
+    #[test]
+    fn submits_once_and_remembers_the_verdict() {
+        let response = "<article><p>That's not the right answer; your answer is too low.";
+        let server = TestServer::start(vec![(200, response.to_string())]);
+        let client = Client::new(server.url(), "secret").with_min_interval(Duration::ZERO);
+        let dir = TempDir::new("submit-remember");
+        let mut verdicts = Verdicts::load(&dir, Year(2015)).unwrap();
+
+        let first = submit_answer(&client, &mut verdicts, Year(2015), Day(7), Part::Two, "42");
+        let again = submit_answer(&client, &mut verdicts, Year(2015), Day(7), Part::Two, "42");
+        let lower = submit_answer(&client, &mut verdicts, Year(2015), Day(7), Part::Two, "41");
+
+        assert_eq!(SubmitOutcome::Submitted(Verdict::TooLow), first.unwrap());
+        assert_eq!(
+            SubmitOutcome::AlreadyJudged(Verdict::TooLow),
+            again.unwrap()
+        );
+        assert!(matches!(lower.unwrap(), SubmitOutcome::OutOfBounds(_)));
+
+        let requests = server.requests();
+        assert_eq!(1, requests.len());
+        assert!(requests[0].starts_with("POST /2015/day/7/answer "));
+        assert!(requests[0].ends_with("level=2&answer=42"));
+
+        let reloaded = Verdicts::load(&dir, Year(2015));
+        assert_eq!(
+            Some(Verdict::TooLow),
+            reloaded.unwrap().previous(Day(7), Part::Two, "42")
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
+
+/// Formats a duration with a unit fitting its magnitude, e.g. `815ns`, `12.3µs` or `1.50s`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    match nanos {
+        0..=999 => format!("{nanos}ns"),
+        1_000..=999_999 => format!("{:.1}µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", nanos as f64 / 1e9),
+    }
+}
+
+/// Summary of repeated measurements of the same thing.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub mean: Duration,
+    #[serde(with = "nanos")]
+    pub deviation: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let count = sorted.len() as f64;
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+
+        Self {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            deviation: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Stores durations as a plain number of nanoseconds.
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_durations_with_fitting_unit() {
+        assert_eq!("815ns", format_duration(Duration::from_nanos(815)));
+        assert_eq!("12.3µs", format_duration(Duration::from_nanos(12_345)));
+        assert_eq!("4.0ms", format_duration(Duration::from_micros(4_000)));
+        assert_eq!("1.50s", format_duration(Duration::from_millis(1_500)));
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_micros(2_500), stats.median);
+        assert_eq!(Duration::from_micros(2_500), stats.mean);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    registered_days, workspace_root, Config, Day, Error, InputLocator, KnownAnswers, Part, Result,
+    Year,
+};
+
+use anyhow::anyhow;
+use std::fmt::{self, Display};
+
+#[derive(Debug)]
+pub enum Check {
+    Matches,
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The solution ran, but there is no known answer to compare with.
+    Unrecorded(String),
+    /// The solution failed for a part without a known answer, it is probably not solved yet.
+    Unsolved(Error),
+    Failed(Error),
+}
+
+#[derive(Debug)]
+pub struct VerifyReport {
+    pub year: Year,
+    pub checks: Vec<(Day, Part, Check)>,
+}
+
+impl VerifyReport {
+    /// No answer differs from the known one and every part ran.
+    pub fn is_success(&self) -> bool {
+        self.checks.iter().all(|(_, _, check)| {
+            matches!(
+                check,
+                Check::Matches | Check::Unrecorded(_) | Check::Unsolved(_)
+            )
+        })
+    }
+}
+
+impl Display for VerifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (day, part, check) in &self.checks {
+            write!(f, "{} day {day} part {part}: ", self.year)?;
+            match check {
+                Check::Matches => writeln!(f, "ok")?,
+                Check::Mismatch { expected, actual } => {
+                    writeln!(f, "MISMATCH, expected {expected}, got {actual}")?
+                }
+                Check::Unrecorded(actual) => writeln!(f, "no known answer, got {actual}")?,
+                Check::Unsolved(error) => writeln!(f, "not solved: {error:#}")?,
+                Check::Failed(error) => writeln!(f, "FAILED: {error:#}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs every registered day of the year against its real input and compares the answers with
+/// the known ones.
+pub fn verify_year(inputs: &InputLocator, known: &KnownAnswers, year: Year) -> VerifyReport {
+    let checks = registered_days(year)
+        .into_iter()
+        .flat_map(|day| {
+            let (part_one, part_two) = match inputs.solve(year, day) {
+                Ok(answers) => (answers.part_one, answers.part_two),
+                Err(error) => (Err(anyhow!("{error:#}")), Err(error)),
+            };
+
+            [
+                (day, Part::One, check(known.get(day, Part::One), part_one)),
+                (day, Part::Two, check(known.get(day, Part::Two), part_two)),
+            ]
+        })
+        .collect();
+
+    VerifyReport { year, checks }
+}
+
+/// [`verify_year`] with the default input lookup and `answers/` in the workspace root.
+pub fn verify(year: Year) -> Result<VerifyReport> {
+    let config = Config::load()?;
+    let inputs = InputLocator::discover(None, &config);
+    let root = workspace_root().ok_or_else(|| anyhow!("Failed to find the workspace root"))?;
+    let known = KnownAnswers::load(&root.join("answers"), year)?;
+
+    Ok(verify_year(&inputs, &known, year))
+}
+
+fn check(expected: Option<&str>, actual: Result<String>) -> Check {
+    match (expected, actual) {
+        (None, Err(error)) => Check::Unsolved(error),
+        (Some(_), Err(error)) => Check::Failed(error),
+        (None, Ok(actual)) => Check::Unrecorded(actual),
+        (Some(expected), Ok(actual)) if expected == actual => Check::Matches,
+        (Some(expected), Ok(actual)) => Check::Mismatch {
+            expected: expected.to_string(),
+            actual,
+        },
+    }
+}
@@ -0,0 +1,404 @@
+//! Pictures of grid puzzles, written as PPM/PNG frames, animated GIFs or colored terminal output.
+
+use crate::anyhow::{bail, Context};
+use crate::{BitGrid, Day, Grid, Part, Result, Year};
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::time::Duration;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Black through red and yellow to white as `fraction` goes from 0 to 1.
+    ///
+    /// There are 256 steps, so a heatmap still fits the palette of a GIF.
+    pub fn heat(fraction: f64) -> Self {
+        let level = (fraction.clamp(0.0, 1.0) * 255.0).round() as u32;
+        let channel = |offset: u32| (level.saturating_sub(offset) * 3).min(255) as u8;
+
+        Self::rgb(channel(0), channel(85), channel(170))
+    }
+
+    fn average(colors: impl Iterator<Item = Color>) -> Self {
+        let (mut sum, mut count) = ([0u32; 3], 0);
+        for color in colors {
+            sum[0] += u32::from(color.r);
+            sum[1] += u32::from(color.g);
+            sum[2] += u32::from(color.b);
+            count += 1;
+        }
+        let channel = |sum: u32| (sum / count.max(1)) as u8;
+
+        Self::rgb(channel(sum[0]), channel(sum[1]), channel(sum[2]))
+    }
+}
+
+/// A single picture, one pixel per grid cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pixels: Grid<Color>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Self {
+            pixels: Grid::new(width, height, background),
+        }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, color: impl FnMut(&T) -> Color) -> Self {
+        Self {
+            pixels: grid.map(color),
+        }
+    }
+
+    pub fn from_bits(bits: &BitGrid, on: Color, off: Color) -> Self {
+        let cells = (0..bits.height())
+            .flat_map(|y| (0..bits.width()).map(move |x| (x, y)))
+            .map(|position| match bits.get(position) {
+                Some(true) => on,
+                _ => off,
+            })
+            .collect();
+
+        Self {
+            pixels: Grid::from_cells(bits.width(), cells),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.pixels.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.height()
+    }
+
+    pub fn pixels(&self) -> &Grid<Color> {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut Grid<Color> {
+        &mut self.pixels
+    }
+
+    /// Scales the frame down, averaging every `factor` by `factor` block into one pixel.
+    pub fn shrink(&self, factor: usize) -> Self {
+        if factor <= 1 {
+            return self.clone();
+        }
+        let (width, height) = (
+            self.width().div_ceil(factor),
+            self.height().div_ceil(factor),
+        );
+        let (max_x, max_y) = (self.width() - 1, self.height() - 1);
+
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x * factor, y * factor)))
+            .map(|(x, y)| {
+                let corner = ((x + factor - 1).min(max_x), (y + factor - 1).min(max_y));
+
+                Color::average(self.pixels.rectangle((x, y), corner).copied())
+            })
+            .collect();
+
+        Self {
+            pixels: Grid::from_cells(width, cells),
+        }
+    }
+
+    /// Writes a binary PPM (`P6`) image.
+    pub fn write_ppm(&self, mut writer: impl Write) -> Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        writer.write_all(&self.rgb_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn write_png(&self, writer: impl Write) -> Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width() as u32, self.height() as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()?
+            .write_image_data(&self.rgb_bytes())?;
+
+        Ok(())
+    }
+
+    /// Draws the frame with 24 bit terminal colors, two pixel rows per line of text.
+    pub fn to_ansi(&self) -> String {
+        let mut rendered = String::new();
+        for y in (0..self.height()).step_by(2) {
+            for x in 0..self.width() {
+                let top = self.pixels[(x, y)];
+                let bottom = self.pixels.get((x, y + 1)).copied().unwrap_or_default();
+                let _ = write!(
+                    rendered,
+                    "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀",
+                    top.r, top.g, top.b, bottom.r, bottom.g, bottom.b
+                );
+            }
+            rendered.push_str("\x1b[0m\n");
+        }
+
+        rendered
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .cells()
+            .flat_map(|color| [color.r, color.g, color.b])
+            .collect()
+    }
+}
+
+/// Writes frames of the same size into an animated GIF that loops forever.
+pub struct GifWriter<W: Write> {
+    writer: Option<W>,
+    encoder: Option<gif::Encoder<W>>,
+    size: (u16, u16),
+    delay: u16,
+}
+
+impl<W: Write> GifWriter<W> {
+    /// `delay` is how long every frame is shown, rounded to hundredths of a second.
+    pub fn new(writer: W, delay: Duration) -> Self {
+        Self {
+            writer: Some(writer),
+            encoder: None,
+            size: (0, 0),
+            delay: (delay.as_millis() / 10).min(u128::from(u16::MAX)) as u16,
+        }
+    }
+
+    pub fn push(&mut self, frame: &Frame) -> Result<()> {
+        let size = (
+            u16::try_from(frame.width()).context("Frame is too wide for a GIF")?,
+            u16::try_from(frame.height()).context("Frame is too high for a GIF")?,
+        );
+
+        let encoder = match (&mut self.encoder, self.writer.take()) {
+            (Some(encoder), _) if size == self.size => encoder,
+            (Some(_), _) => bail!(
+                "Frame of {}x{} doesn't match the animation of {}x{}",
+                size.0,
+                size.1,
+                self.size.0,
+                self.size.1
+            ),
+            (None, Some(writer)) => {
+                let mut encoder = gif::Encoder::new(writer, size.0, size.1, &[])?;
+                encoder.set_repeat(gif::Repeat::Infinite)?;
+                self.size = size;
+                self.encoder.insert(encoder)
+            }
+            (None, None) => unreachable!("GifWriter has neither a writer nor an encoder"),
+        };
+
+        let mut gif_frame = indexed_frame(frame, size)
+            .unwrap_or_else(|| gif::Frame::from_rgb_speed(size.0, size.1, &frame.rgb_bytes(), 10));
+        gif_frame.delay = self.delay;
+        encoder.write_frame(&gif_frame)?;
+
+        Ok(())
+    }
+
+    /// Finishes the animation and hands back the writer.
+    pub fn finish(self) -> Result<W> {
+        let Some(encoder) = self.encoder else {
+            bail!("An animation needs at least one frame");
+        };
+
+        Ok(encoder.into_inner()?)
+    }
+}
+
+/// A frame using its own colors as the palette, if there are at most 256 of them.
+fn indexed_frame(frame: &Frame, (width, height): (u16, u16)) -> Option<gif::Frame<'static>> {
+    let mut palette = Vec::new();
+    let mut known = HashMap::new();
+    let mut indices = Vec::with_capacity(frame.width() * frame.height());
+    for &color in frame.pixels.cells() {
+        let index = match known.get(&color) {
+            Some(&index) => index,
+            None if palette.len() < 256 => {
+                let index = palette.len() as u8;
+                palette.push(color);
+                known.insert(color, index);
+                index
+            }
+            None => return None,
+        };
+        indices.push(index);
+    }
+
+    let palette = palette.iter().flat_map(|color| [color.r, color.g, color.b]);
+
+    Some(gif::Frame::from_palette_pixels(
+        width,
+        height,
+        indices,
+        palette.collect::<Vec<_>>(),
+        None,
+    ))
+}
+
+/// Collects frames from a [`Visualizer`], keeping every `every`-th step and the final state.
+pub struct Recorder<'a> {
+    every: usize,
+    steps: usize,
+    pending: bool,
+    sink: &'a mut dyn FnMut(Frame) -> Result<()>,
+}
+
+impl<'a> Recorder<'a> {
+    pub fn new(every: usize, sink: &'a mut dyn FnMut(Frame) -> Result<()>) -> Self {
+        Self {
+            every: every.max(1),
+            steps: 0,
+            pending: false,
+            sink,
+        }
+    }
+
+    /// Records the state after a step, drawing it only if the frame is kept.
+    pub fn step(&mut self, frame: impl FnOnce() -> Frame) -> Result<()> {
+        self.steps += 1;
+        self.pending = !(self.steps - 1).is_multiple_of(self.every);
+        if self.pending {
+            return Ok(());
+        }
+
+        (self.sink)(frame())
+    }
+
+    /// Records the final state, unless its step was already kept.
+    pub fn finish(&mut self, frame: impl FnOnce() -> Frame) -> Result<()> {
+        if !self.pending {
+            return Ok(());
+        }
+        self.pending = false;
+
+        (self.sink)(frame())
+    }
+}
+
+/// Draws the steps of a day for one of its parts.
+pub type Visualizer = fn(&str, Part, &mut Recorder) -> Result<()>;
+
+/// A day that can be drawn, submitted with [`register_visualizer!`](crate::register_visualizer).
+#[derive(Debug)]
+pub struct Visualization {
+    pub year: Year,
+    pub day: Day,
+    pub visualizer: Visualizer,
+}
+
+inventory::collect!(Visualization);
+
+/// Registers a [`Visualizer`] for the given year and day.
+///
+/// ```ignore
+/// common::register_visualizer!(2015, 6, visualize);
+/// ```
+#[macro_export]
+macro_rules! register_visualizer {
+    ($year:expr, $day:expr, $visualizer:path) => {
+        $crate::inventory::submit! {
+            $crate::viz::Visualization {
+                year: $crate::Year($year),
+                day: $crate::Day($day),
+                visualizer: $visualizer,
+            }
+        }
+    };
+}
+
+pub fn visualizer(year: Year, day: Day) -> Option<Visualizer> {
+    inventory::iter::<Visualization>
+        .into_iter()
+        .find(|visualization| visualization.year == year && visualization.day == day)
+        .map(|visualization| visualization.visualizer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Frame {
+        let grid = Grid::from_cells(2, vec![true, false, false, true]);
+
+        Frame::from_grid(&grid, |&on| if on { Color::WHITE } else { Color::BLACK })
+    }
+
+    #[test]
+    fn writes_ppm() {
+        let mut ppm = Vec::new();
+        checkerboard().write_ppm(&mut ppm).unwrap();
+
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]);
+        assert_eq!(expected, ppm);
+    }
+
+    #[test]
+    fn writes_png_and_gif() {
+        let mut png = Vec::new();
+        checkerboard().write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let mut gif = GifWriter::new(Vec::new(), Duration::from_millis(100));
+        gif.push(&checkerboard()).unwrap();
+        gif.push(&checkerboard()).unwrap();
+        assert!(gif.push(&Frame::new(3, 3, Color::BLACK)).is_err());
+        assert!(gif.finish().unwrap().starts_with(b"GIF89a"));
+    }
+
+    #[test]
+    fn shrinks_by_averaging() {
+        let shrunk = checkerboard().shrink(2);
+
+        assert_eq!((1, 1), (shrunk.width(), shrunk.height()));
+        assert_eq!(Color::rgb(127, 127, 127), shrunk.pixels()[(0, 0)]);
+    }
+
+    #[test]
+    fn heat_goes_from_black_to_white() {
+        assert_eq!(Color::BLACK, Color::heat(0.0));
+        assert_eq!(Color::WHITE, Color::heat(1.0));
+        assert_eq!(Color::rgb(255, 0, 0), Color::heat(85.0 / 255.0));
+    }
+
+    #[test]
+    fn records_every_nth_step_and_the_final_state() {
+        let mut recorded = Vec::new();
+        let mut sink = |frame: Frame| {
+            recorded.push(frame.width());
+            Ok(())
+        };
+        let mut recorder = Recorder::new(3, &mut sink);
+        for width in 1..=5 {
+            recorder
+                .step(|| Frame::new(width, 1, Color::BLACK))
+                .unwrap();
+        }
+        recorder.finish(|| Frame::new(5, 1, Color::BLACK)).unwrap();
+
+        assert_eq!(vec![1, 4, 5], recorded);
+    }
+}
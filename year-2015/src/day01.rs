@@ -1,4 +1,4 @@
-use common::{read_single_input_line, Day, Result, Year};
+use common::{register, Result, Solution};
 
 fn delta(floor_symbol: char) -> i64 {
     match floor_symbol {
@@ -15,25 +15,36 @@ fn calculate_floor(input: &str) -> i64 {
 fn calculate_basement_position(input: &str) -> usize {
     let mut current_floor = 0;
     for (position, floor) in input.chars().enumerate() {
+        current_floor += delta(floor);
+
         if current_floor == -1 {
-            return position;
+            return position + 1;
         }
-
-        current_floor += delta(floor);
     }
 
     0
 }
 
-fn main() -> Result<()> {
-    let input = read_single_input_line(Year(2015), Day(1))?;
-    let floor = calculate_floor(&input);
-    let basement_pos = calculate_basement_position(&input);
+pub struct Day01;
 
-    println!("Floor: {floor}");
-    println!("Basement: {basement_pos}");
+register!(2015, 1, Day01);
 
-    Ok(())
+impl Solution for Day01 {
+    type Input = String;
+    type PartOne = i64;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne> {
+        Ok(calculate_floor(input))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(calculate_basement_position(input))
+    }
 }
 
 #[cfg(test)]
@@ -41,20 +52,28 @@ mod tests {
     use super::*;
 
     #[test]
-    fn calculates_floor() {
-        let input = ")))(()((()(()(";
-        let floor = calculate_floor(input);
-        let expected_floor = 2;
-
-        assert_eq!(expected_floor, floor);
+    fn finds_the_basement_on_the_last_character() {
+        assert_eq!(3, calculate_basement_position("())"));
     }
 
-    #[test]
-    fn calculates_basement_position() {
-        let input = "(())()())(()()())))(()()(";
-        let basement_position = calculate_basement_position(input);
-        let expected_position = 9;
-
-        assert_eq!(expected_position, basement_position);
+    common::examples! {
+        Day01,
+        part_one {
+            balanced: "(())" => 0,
+            alternating: "()()" => 0,
+            three_up: "(((" => 3,
+            three_up_with_descents: "(()(()(" => 3,
+            ends_above_after_descending: "))(((((" => 3,
+            one_below: "())" => -1,
+            starts_below: "))(" => -1,
+            three_below: ")))" => -3,
+            three_below_mixed: ")())())" => -3,
+            longer: ")))(()((()(()(" => 2,
+        }
+        part_two {
+            first_character: ")" => 1,
+            fifth_character: "()())" => 5,
+            longer: "(())()())(()()())))(()()(" => 9,
+        }
     }
 }
@@ -1,7 +1,7 @@
-use common::{anyhow, anyhow::Result, read_input_lines, Day, InputLines, Year};
+use common::{parse_lines, register, InputError, Result, Solution};
 use std::str::FromStr;
 
-struct Gift {
+pub struct Gift {
     length: u64,
     width: u64,
     height: u64,
@@ -27,15 +27,20 @@ impl Gift {
 }
 
 impl FromStr for Gift {
-    type Err = anyhow::Error;
+    type Err = InputError;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let dimension = |side: &str| {
+            side.parse()
+                .map_err(|error| InputError::invalid(side, error).within(string, side))
+        };
+
         let split: Vec<_> = string.split('x').collect();
         match split.as_slice() {
             [length, width, height] => {
-                let length = length.parse()?;
-                let width = width.parse()?;
-                let height = height.parse()?;
+                let length = dimension(length)?;
+                let width = dimension(width)?;
+                let height = dimension(height)?;
 
                 Ok(Self {
                     length,
@@ -43,18 +48,16 @@ impl FromStr for Gift {
                     height,
                 })
             }
-            _ => anyhow::bail!("Expected 'length x width x height' format, got {split:?}"),
+            _ => Err(InputError::invalid(
+                string,
+                "Expected 'length x width x height' format",
+            )),
         }
     }
 }
 
-fn gifts(input: InputLines) -> Result<Vec<Gift>> {
-    input
-        .map(|string| {
-            let string = string?;
-            Gift::from_str(&string)
-        })
-        .collect()
+fn gifts(input: &str) -> Result<Vec<Gift>> {
+    Ok(parse_lines(input, Gift::from_str)?)
 }
 
 fn paper_needed(gifts: &[Gift]) -> u64 {
@@ -69,16 +72,26 @@ fn ribbon_needed(gifts: &[Gift]) -> u64 {
     })
 }
 
-fn main() -> Result<()> {
-    let input = read_input_lines(Year(2015), Day(2))?;
-    let gifts = gifts(input)?;
-    let paper_needed = paper_needed(&gifts);
-    let ribbon_needed = ribbon_needed(&gifts);
+pub struct Day02;
+
+register!(2015, 2, Day02);
+
+impl Solution for Day02 {
+    type Input = Vec<Gift>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        gifts(input)
+    }
 
-    println!("Paper needed: {paper_needed}");
-    println!("Ribbon needed: {ribbon_needed}");
+    fn part_one(gifts: &Self::Input) -> Result<Self::PartOne> {
+        Ok(paper_needed(gifts))
+    }
 
-    Ok(())
+    fn part_two(gifts: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(ribbon_needed(gifts))
+    }
 }
 
 #[cfg(test)]
@@ -96,4 +109,18 @@ mod tests {
         assert_eq!(24, gift.paper_needed());
         assert_eq!(12, gift.ribbon_needed());
     }
+
+    common::examples! {
+        Day02,
+        part_one {
+            cuboid: "2x3x4" => 58,
+            long_and_thin: "1x1x10" => 43,
+            both: "2x3x4\n1x1x10" => 101,
+        }
+        part_two {
+            cuboid: "2x3x4" => 34,
+            long_and_thin: "1x1x10" => 14,
+            both: "2x3x4\n1x1x10" => 48,
+        }
+    }
 }
@@ -1,169 +1,165 @@
-use common::{read_single_input_line, Day, Result, Year};
-
-use std::collections::HashSet;
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    pub fn vector(&self) -> (isize, isize) {
-        match self {
-            Direction::Up => (0, 1),
-            Direction::Down => (0, -1),
-            Direction::Left => (-1, 0),
-            Direction::Right => (1, 0),
-        }
+use common::geometry::{Direction, Point, Rect, SparseGrid};
+use common::viz::{Color, Frame, Recorder};
+use common::{parse_lines, register, register_visualizer, InputError, Part, Result, Solution};
+
+/// Parses a move, which the elf only writes as an arrow.
+fn arrow(symbol: char) -> Result<Direction, InputError> {
+    match symbol {
+        '^' => Ok(Direction::Up),
+        '>' => Ok(Direction::Right),
+        'v' => Ok(Direction::Down),
+        '<' => Ok(Direction::Left),
+        _ => Err(InputError::invalid(symbol, "Expected one of '^v<>'")),
     }
 }
 
-impl From<char> for Direction {
-    fn from(c: char) -> Self {
-        match c {
-            '^' => Self::Up,
-            'v' => Self::Down,
-            '<' => Self::Left,
-            '>' => Self::Right,
-            _ => unreachable!(),
-        }
-    }
-}
+/// Which of the santas, taking turns, moves to which house.
+fn moves(santas: usize, directions: &[Direction]) -> impl Iterator<Item = (usize, Point)> + '_ {
+    let mut positions = vec![Point::ORIGIN; santas];
 
-#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
-struct Position {
-    x: isize,
-    y: isize,
-}
+    directions.iter().enumerate().map(move |(n, &direction)| {
+        let santa = n % santas;
+        positions[santa] = positions[santa].step(direction);
 
-impl Position {
-    pub fn r#move(&mut self, direction: Direction) {
-        let (x, y) = direction.vector();
-
-        self.x += x;
-        self.y += y;
-    }
+        (santa, positions[santa])
+    })
 }
 
-fn houses_visited(input: &str) -> usize {
-    let mut santas_position = Position::default();
+/// Counts the presents delivered to every house, starting with the one at the origin.
+fn deliver(santas: usize, directions: &[Direction]) -> SparseGrid<u32> {
+    let mut houses = SparseGrid::new();
+    houses.insert(Point::ORIGIN, santas as u32);
 
-    let house_positions: HashSet<_> = input
-        .chars()
-        .map(|symbol| {
-            let direction = Direction::from(symbol);
-            santas_position.r#move(direction);
+    for (_, position) in moves(santas, directions) {
+        *houses.entry(position).or_insert(0) += 1;
+    }
 
-            santas_position
-        })
-        .chain(std::iter::once(Position::default())) // include the initial position as well
-        .collect();
+    houses
+}
 
-    house_positions.len()
+fn houses_visited(directions: &[Direction]) -> usize {
+    deliver(1, directions).len()
 }
 
-fn houses_visited_with_robo_santa(input: &str) -> usize {
-    let mut santas_position = Position::default();
-    let mut robos_position = Position::default();
-
-    let house_positions: HashSet<_> = input
-        .chars()
-        .enumerate()
-        .map(|(n, symbol)| {
-            let direction = Direction::from(symbol);
-
-            if n % 2 == 0 {
-                santas_position.r#move(direction);
-                santas_position
-            } else {
-                robos_position.r#move(direction);
-                robos_position
-            }
-        })
-        .chain(std::iter::once(Position::default())) // include the initial position as well
-        .collect();
-
-    house_positions.len()
+fn houses_visited_with_robo_santa(directions: &[Direction]) -> usize {
+    deliver(2, directions).len()
 }
 
-fn main() -> Result<()> {
-    let input = read_single_input_line(Year(2015), Day(3))?;
-    let houses_visited = houses_visited(&input);
-    let houses_visited_with_robo = houses_visited_with_robo_santa(&input);
+/// Draws the houses as they are visited, Santa's in red, Robo-Santa's in green and shared ones in
+/// white.
+fn visualize(input: &str, part: Part, recorder: &mut Recorder) -> Result<()> {
+    let directions = Day03::parse(input)?;
+    let santas = match part {
+        Part::One => 1,
+        Part::Two => 2,
+    };
+    let positions = moves(santas, &directions).map(|(_, position)| position);
+    let bounds = Rect::bounding(positions.chain([Point::ORIGIN])).expect("The origin is a house");
+
+    // A bit per santa that visited the house.
+    let mut visitors = SparseGrid::new();
+    visitors.insert(Point::ORIGIN, (1 << santas) - 1);
+    for (santa, position) in moves(santas, &directions) {
+        *visitors.entry(position).or_insert(0) |= 1 << santa;
+        recorder.step(|| houses_frame(&visitors, bounds))?;
+    }
+
+    recorder.finish(|| houses_frame(&visitors, bounds))
+}
 
-    println!("Houses visited: {houses_visited}");
-    println!("Houses visited with Robo-Santa: {houses_visited_with_robo}");
+fn houses_frame(visitors: &SparseGrid<u8>, bounds: Rect) -> Frame {
+    let mut frame = Frame::new(
+        bounds.width() as usize,
+        bounds.height() as usize,
+        Color::BLACK,
+    );
+    for (house, &visitors) in visitors.iter() {
+        let position = (house - bounds.min)
+            .to_grid()
+            .expect("Bounds contain every house");
+        frame.pixels_mut()[position] = match visitors {
+            0b01 => Color::rgb(220, 40, 40),
+            0b10 => Color::rgb(40, 200, 80),
+            _ => Color::WHITE,
+        };
+    }
 
-    Ok(())
+    frame
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day03;
 
-    // The tests are taken from the examples in the description
-    // Part 1
+register!(2015, 3, Day03);
+register_visualizer!(2015, 3, visualize);
 
-    // > delivers presents to 2 houses: one at the starting location, and one to the east.
-    #[test]
-    fn simple() {
-        let input = ">";
-        let houses_visited = houses_visited(input);
+impl Solution for Day03 {
+    type Input = Vec<Direction>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-        assert_eq!(2, houses_visited);
-    }
+    fn parse(input: &str) -> Result<Self::Input> {
+        let directions = parse_lines(input.trim(), |line| {
+            line.char_indices()
+                .map(|(offset, symbol)| {
+                    let text = &line[offset..offset + symbol.len_utf8()];
 
-    // ^>v< delivers presents to 4 houses in a square,
-    // including twice to the house at his starting/ending location.
-    #[test]
-    fn houses_in_a_square() {
-        let input = "^>v<";
-        let houses_visited = houses_visited(input);
+                    arrow(symbol).map_err(|error| error.within(line, text))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })?;
 
-        assert_eq!(4, houses_visited);
+        Ok(directions.concat())
     }
 
-    // ^v^v^v^v^v delivers a bunch of presents to
-    // some very lucky children at only 2 houses.
-    #[test]
-    fn up_and_down() {
-        let input = "^v^v^v^v^v";
-        let houses_visited = houses_visited(input);
-
-        assert_eq!(2, houses_visited);
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne> {
+        Ok(houses_visited(input))
     }
 
-    // Part 2
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(houses_visited_with_robo_santa(input))
+    }
+}
 
-    // ^v delivers presents to 3 houses, because Santa goes north, and then Robo-Santa goes south.
-    #[test]
-    fn robo_santa() {
-        let input = "^v";
-        let houses_visited = houses_visited_with_robo_santa(input);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        assert_eq!(3, houses_visited);
+    // The examples are taken from the description
+    common::examples! {
+        Day03,
+        part_one {
+            // > delivers presents to 2 houses: one at the starting location, and one to the east.
+            simple: ">" => 2,
+            // ^>v< delivers presents to 4 houses in a square,
+            // including twice to the house at his starting/ending location.
+            houses_in_a_square: "^>v<" => 4,
+            // ^v^v^v^v^v delivers a bunch of presents to
+            // some very lucky children at only 2 houses.
+            up_and_down: "^v^v^v^v^v" => 2,
+        }
+        part_two {
+            // ^v delivers presents to 3 houses, because Santa goes north, and then Robo-Santa goes south.
+            robo_santa: "^v" => 3,
+            // ^>v< now delivers presents to 3 houses, and Santa and Robo-Santa
+            // end up back where they started.
+            both_back_and_forth: "^>v<" => 3,
+            // ^v^v^v^v^v now delivers presents to 11 houses, with Santa going
+            // one direction and Robo-Santa going the other
+            both_going_away: "^v^v^v^v^v" => 11,
+        }
     }
 
-    // ^>v< now delivers presents to 3 houses, and Santa and Robo-Santa
-    // end up back where they started.
     #[test]
-    fn both_back_and_forth() {
-        let input = "^>v<";
-        let houses_visited = houses_visited_with_robo_santa(input);
+    fn reports_unknown_directions() {
+        let error = Day03::parse("^>x<").unwrap_err();
 
-        assert_eq!(3, houses_visited);
+        assert_eq!("input:1:3: Expected one of '^v<>': 'x'", error.to_string());
     }
 
-    // ^v^v^v^v^v now delivers presents to 11 houses, with Santa going
-    // one direction and Robo-Santa going the other
     #[test]
-    fn both_going_away() {
-        let input = "^v^v^v^v^v";
-        let houses_visited = houses_visited_with_robo_santa(input);
+    fn only_accepts_arrows() {
+        let error = Day03::parse("^N").unwrap_err();
 
-        assert_eq!(11, houses_visited);
+        assert_eq!("input:1:2: Expected one of '^v<>': 'N'", error.to_string());
     }
 }
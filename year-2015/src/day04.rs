@@ -1,40 +1,52 @@
 use common::anyhow::anyhow;
-use common::{read_single_input_line, Day, Result, Year};
+use common::hash::{find_nonce, HexPrefix};
+use common::{register, Result, Solution};
 
-fn find_number_for_md5(input: &str, starting_with: &str) -> Option<usize> {
-    (0..).find(|number| {
-        let hash = md5::compute(format!("{input}{number}"));
-        let hash_in_hex = format!("{hash:x}"); // probably could be done better without allocating?
+use std::sync::atomic::AtomicBool;
 
-        hash_in_hex.starts_with(starting_with)
-    })
+fn find_number_for_md5(key: &str, starting_with: &str) -> Result<Option<u64>> {
+    Ok(find_nonce(
+        key,
+        HexPrefix::new(starting_with)?,
+        &AtomicBool::new(false),
+    ))
 }
 
-fn main() -> Result<()> {
-    let input = read_single_input_line(Year(2015), Day(4))?;
-    let with_five_zeroes = find_number_for_md5(&input, "00000")
-        .ok_or_else(|| anyhow!("Failed to get number for part 1"))?;
-    let with_six_zeroes = find_number_for_md5(&input, "000000")
-        .ok_or_else(|| anyhow!("Failed to get number for part 2"))?;
+pub struct Day04;
 
-    println!("The number for part 1 is: {with_five_zeroes}");
-    println!("The number for part 2 is: {with_six_zeroes}");
+register!(2015, 4, Day04);
 
-    Ok(())
+impl Solution for Day04 {
+    type Input = String;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne> {
+        find_number_for_md5(input, "00000")?
+            .ok_or_else(|| anyhow!("Failed to get number for part 1"))
+    }
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo> {
+        find_number_for_md5(input, "000000")?
+            .ok_or_else(|| anyhow!("Failed to get number for part 2"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    // If your secret key is abcdef, the answer is 609043,
-    // because the MD5 hash of abcdef609043 starts with five zeroes (000001dbbfa...),
-    // and it is the lowest such number to do so.
-    #[test]
-    fn first_example_from_task_description() {
-        let input = "abcdef";
-        let expected = Some(609043);
-
-        assert_eq!(expected, find_number_for_md5(input, "00000"));
+    common::examples! {
+        Day04,
+        part_one {
+            // If your secret key is abcdef, the answer is 609043,
+            // because the MD5 hash of abcdef609043 starts with five zeroes (000001dbbfa...),
+            // and it is the lowest such number to do so.
+            first_example_from_task_description: "abcdef" => 609043,
+        }
     }
 }
@@ -1,4 +1,4 @@
-use common::{read_input_lines, Day, InputLines, Result, Year};
+use common::{register, Result, Solution};
 use itertools::Itertools;
 use std::collections::HashMap;
 
@@ -64,41 +64,52 @@ impl NiceWordsSolver for NiceWordsSolverV2 {
     }
 }
 
-fn words(input_lines: InputLines) -> Result<Vec<String>> {
-    input_lines.map(|line| Ok(line?)).collect()
+fn words(input: &str) -> Vec<String> {
+    input.lines().map(ToOwned::to_owned).collect()
 }
 
-fn main() -> Result<()> {
-    let input = read_input_lines(Year(2015), Day(5))?;
-    let words = words(input)?;
-    let nice_words = NiceWordsSolverV1::count_nice_words(&words);
-    let nice_words_v2 = NiceWordsSolverV2::count_nice_words(&words);
+pub struct Day05;
 
-    println!("Number of nice words: {nice_words}");
-    println!("Number of nice words (v2): {nice_words_v2}");
+register!(2015, 5, Day05);
 
-    Ok(())
+impl Solution for Day05 {
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(words(input))
+    }
+
+    fn part_one(words: &Self::Input) -> Result<Self::PartOne> {
+        Ok(NiceWordsSolverV1::count_nice_words(words))
+    }
+
+    fn part_two(words: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(NiceWordsSolverV2::count_nice_words(words))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn v1_examples() {
-        assert!(NiceWordsSolverV1::is_nice("ugknbfddgicrmopn"));
-        assert!(NiceWordsSolverV1::is_nice("aaa"));
-        assert!(!NiceWordsSolverV1::is_nice("jchzalrnumimnmhp"));
-        assert!(!NiceWordsSolverV1::is_nice("haegwjzuvuyypxyu"));
-        assert!(!NiceWordsSolverV1::is_nice("dvszwmarrgswjxmb"));
-    }
-
-    #[test]
-    fn v2_examples() {
-        assert!(NiceWordsSolverV2::is_nice("qjhvhtzxzqqjkmpb"));
-        assert!(NiceWordsSolverV2::is_nice("xxyxx"));
-        assert!(!NiceWordsSolverV2::is_nice("uurcxstgmygtbstg"));
-        assert!(!NiceWordsSolverV2::is_nice("ieodomkazucvgmuy"));
-        assert!(!NiceWordsSolverV2::is_nice("aaa"));
+    common::examples! {
+        Day05,
+        part_one {
+            nice: "ugknbfddgicrmopn" => 1,
+            nice_with_overlapping_rules: "aaa" => 1,
+            no_double_letter: "jchzalrnumimnmhp" => 0,
+            naughty_string: "haegwjzuvuyypxyu" => 0,
+            single_vowel: "dvszwmarrgswjxmb" => 0,
+            all_together: "ugknbfddgicrmopn\naaa\njchzalrnumimnmhp" => 2,
+        }
+        part_two {
+            nice: "qjhvhtzxzqqjkmpb" => 1,
+            nice_with_overlapping_rules: "xxyxx" => 1,
+            no_repeat_with_separator: "uurcxstgmygtbstg" => 0,
+            no_repeated_pair: "ieodomkazucvgmuy" => 0,
+            overlapping_pair: "aaa" => 0,
+        }
     }
 }
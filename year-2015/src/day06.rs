@@ -1,60 +1,54 @@
-use common::anyhow::{anyhow, bail, Error};
-use common::{read_input_lines, Day, InputLines, Result, Year};
+use common::anyhow::anyhow;
+use common::geometry::{Point, Rect};
+use common::viz::{Color, Frame, Recorder};
+use common::{
+    parse_lines, register, register_visualizer, BitGrid, InputError, Part, Regions, Result,
+    Solution,
+};
 
 use std::ops::{Add, Sub};
 use std::str::FromStr;
 
 #[derive(Debug)]
-struct Point {
-    pub x: usize,
-    pub y: usize,
-}
-
-impl FromStr for Point {
-    type Err = Error;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let mut split = s.split(',');
-        let x = split
-            .next()
-            .ok_or_else(|| anyhow!("Failed to get x coordinate from '{s}'"))?
-            .parse()?;
-        let y = split
-            .next()
-            .ok_or_else(|| anyhow!("Failed to get y coordinate from '{s}'"))?
-            .parse()?;
-
-        if let Some(unexpected) = split.next() {
-            bail!("Unexpected additional parameter: {unexpected}");
-        }
-
-        Ok(Self { x, y })
-    }
-}
-
-#[derive(Debug)]
-struct Command {
+pub struct Command {
     start: Point,
     end: Point,
     action: Action,
 }
 
 impl Command {
-    pub fn parse(string: String) -> Result<Self> {
+    pub fn parse(string: &str) -> Result<Self, InputError> {
         let split: Vec<_> = string.split(' ').collect();
 
         let (action, start, end) = match *split.as_slice() {
             ["turn", "on", point_x, "through", point_y] => (Action::TurnOn, point_x, point_y),
             ["turn", "off", point_x, "through", point_y] => (Action::TurnOff, point_x, point_y),
             ["toggle", point_x, "through", point_y] => (Action::Toggle, point_x, point_y),
-            _ => bail!("Unknown pattern: {split:?})"),
+            _ => return Err(InputError::invalid(string, "Unknown pattern")),
         };
 
-        let start = Point::from_str(start)?;
-        let end = Point::from_str(end)?;
+        let point = |point| Point::from_str(point).map_err(|error| error.within(string, point));
+        let start = point(start)?;
+        let end = point(end)?;
 
         Ok(Self { start, end, action })
     }
+
+    fn rect(&self) -> Rect {
+        Rect::new(self.start, self.end)
+    }
+
+    /// Grid positions of both corners, if they are on the grid.
+    fn corners(&self) -> Result<((usize, usize), (usize, usize))> {
+        let position = |point: Point| {
+            point
+                .to_grid()
+                .filter(|&(x, y)| x < SIZE && y < SIZE)
+                .ok_or_else(|| anyhow!("{point} is outside of the light grid"))
+        };
+
+        Ok((position(self.start)?, position(self.end)?))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -64,8 +58,8 @@ enum Action {
     TurnOff,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
-struct Brightness(usize);
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Brightness(u64);
 
 impl Add for Brightness {
     type Output = Self;
@@ -83,127 +77,186 @@ impl Sub for Brightness {
     }
 }
 
-#[derive(Debug, Default, Copy, Clone)]
-struct Light {
-    status: LightStatus,    // for part 1
-    brightness: Brightness, // for part 2
-}
+const SIZE: usize = 1000;
 
-#[derive(Debug, Clone, Copy)]
-enum LightStatus {
-    On,
-    Off,
+/// Which lights are on, for part one.
+#[derive(Debug)]
+struct LightGrid {
+    lights: BitGrid,
 }
 
-impl Default for LightStatus {
-    fn default() -> Self {
-        Self::Off
+impl LightGrid {
+    pub fn new() -> Self {
+        Self {
+            lights: BitGrid::new(SIZE, SIZE),
+        }
     }
-}
 
-impl Light {
-    pub fn toggle(&mut self) {
-        self.status = match self.status {
-            LightStatus::On => LightStatus::Off,
-            LightStatus::Off => LightStatus::On,
-        };
-        self.brightness.0 += 2;
+    #[cfg(test)]
+    pub fn new_lit() -> Self {
+        let mut grid = Self::new();
+        grid.lights.set_rectangle((0, 0), (SIZE - 1, SIZE - 1));
+
+        grid
+    }
+
+    pub fn apply(&mut self, command: &Command) -> Result<()> {
+        let (start, end) = command.corners()?;
+
+        match command.action {
+            Action::Toggle => self.lights.toggle_rectangle(start, end),
+            Action::TurnOn => self.lights.set_rectangle(start, end),
+            Action::TurnOff => self.lights.clear_rectangle(start, end),
+        }
+
+        Ok(())
+    }
+
+    pub fn lights_on_count(&self) -> u64 {
+        self.lights.count_ones()
+    }
+
+    pub fn frame(&self) -> Frame {
+        Frame::from_bits(&self.lights, Color::rgb(255, 214, 90), Color::BLACK)
     }
 }
 
+/// How bright every light is, for part two.
+///
+/// The lights are cut into blocks along the edges of the commands that will be applied.
 #[derive(Debug)]
-struct Grid {
-    lights: Vec<Vec<Light>>,
+struct BrightnessGrid {
+    lights: Regions<Brightness>,
 }
 
-impl Grid {
-    const SIZE: usize = 1000;
+impl BrightnessGrid {
+    pub fn new(commands: &[Command]) -> Self {
+        let bounds = Rect::new(Point::ORIGIN, Point::new(SIZE as i64 - 1, SIZE as i64 - 1));
+        let rects = commands.iter().map(Command::rect);
 
-    pub fn new() -> Self {
         Self {
-            lights: vec![vec![Light::default(); Self::SIZE]; Self::SIZE],
+            lights: Regions::new(bounds, rects, Brightness::default()),
         }
     }
 
-    #[cfg(test)]
-    pub fn new_lit() -> Self {
-        Self {
-            lights: vec![
-                vec![
-                    Light {
-                        status: LightStatus::On,
-                        brightness: Brightness::default()
-                    };
-                    Self::SIZE
-                ];
-                Self::SIZE
-            ],
-        }
-    }
+    /// Applies a command, splitting blocks first if it wasn't passed to [`BrightnessGrid::new`].
+    pub fn apply(&mut self, command: &Command) -> Result<()> {
+        command.corners()?;
 
-    pub fn apply(&mut self, command: Command) -> Result<()> {
-        for x in command.start.x..=command.end.x {
-            for y in command.start.y..=command.end.y {
-                let mut old_value = self.lights[x][y];
-                let new_value = match command.action {
-                    Action::Toggle => {
-                        old_value.toggle();
-
-                        old_value
-                    }
-                    Action::TurnOn => Light {
-                        status: LightStatus::On,
-                        brightness: old_value.brightness + Brightness(1),
-                    },
-                    Action::TurnOff => Light {
-                        status: LightStatus::Off,
-                        brightness: old_value.brightness - Brightness(1),
-                    },
-                };
-
-                self.lights[x][y] = new_value;
-            }
-        }
+        self.lights
+            .update(command.rect(), |brightness| match command.action {
+                Action::Toggle => *brightness = *brightness + Brightness(2),
+                Action::TurnOn => *brightness = *brightness + Brightness(1),
+                Action::TurnOff => *brightness = *brightness - Brightness(1),
+            });
 
         Ok(())
     }
 
-    pub fn lights_on_count(&self) -> usize {
-        self.lights
-            .iter()
-            .flatten()
-            .filter(|light| matches!(light.status, LightStatus::On))
-            .count()
+    pub fn total_brightness(&self) -> Brightness {
+        Brightness(self.lights.sum(|brightness| brightness.0))
     }
 
-    pub fn total_brightness(&self) -> Brightness {
+    pub fn brightest(&self) -> Brightness {
         self.lights
-            .iter()
-            .flatten()
-            .fold(Brightness(0), |brightness, light| {
-                brightness + light.brightness
-            })
+            .blocks()
+            .map(|(_, &brightness)| brightness)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// A heatmap relative to the `brightest` light.
+    pub fn frame(&self, brightest: Brightness) -> Frame {
+        let mut frame = Frame::new(SIZE, SIZE, Color::BLACK);
+        for (rect, brightness) in self.lights.blocks() {
+            let corners = (rect.min.to_grid(), rect.max.to_grid());
+            let (Some(start), Some(end)) = corners else {
+                continue;
+            };
+            let color = Color::heat(brightness.0 as f64 / brightest.0.max(1) as f64);
+
+            frame
+                .pixels_mut()
+                .rectangle_mut(start, end)
+                .for_each(|pixel| *pixel = color);
+        }
+
+        frame
     }
 }
 
-fn parse_commands(input_lines: InputLines) -> Result<Vec<Command>> {
-    input_lines.map(|line| Command::parse(line?)).collect()
+fn parse_commands(input: &str) -> Result<Vec<Command>> {
+    Ok(parse_lines(input, Command::parse)?)
 }
 
-fn main() -> Result<()> {
-    let input = read_input_lines(Year(2015), Day(6))?;
-    let commands = parse_commands(input)?;
+fn count_lights_on(commands: &[Command]) -> Result<u64> {
+    let mut grid = LightGrid::new();
+    for command in commands {
+        grid.apply(command)?;
+    }
 
-    let mut grid = Grid::new();
+    Ok(grid.lights_on_count())
+}
 
+fn total_brightness(commands: &[Command]) -> Result<Brightness> {
+    let mut grid = BrightnessGrid::new(commands);
     for command in commands {
         grid.apply(command)?;
     }
 
-    println!("Lights on: {}", grid.lights_on_count());
-    println!("Total brightness: {:?}", grid.total_brightness().0);
+    Ok(grid.total_brightness())
+}
 
-    Ok(())
+/// Draws the lights after every command, as on or off for part one and as a heatmap of their
+/// brightness for part two.
+fn visualize(input: &str, part: Part, recorder: &mut Recorder) -> Result<()> {
+    let commands = parse_commands(input)?;
+
+    match part {
+        Part::One => {
+            let mut grid = LightGrid::new();
+            for command in &commands {
+                grid.apply(command)?;
+                recorder.step(|| grid.frame())?;
+            }
+
+            recorder.finish(|| grid.frame())
+        }
+        Part::Two => {
+            let mut grid = BrightnessGrid::new(&commands);
+            let mut brightest = Brightness(0);
+            for command in &commands {
+                grid.apply(command)?;
+                brightest = brightest.max(grid.brightest());
+                recorder.step(|| grid.frame(brightest))?;
+            }
+
+            recorder.finish(|| grid.frame(brightest))
+        }
+    }
+}
+
+pub struct Day06;
+
+register!(2015, 6, Day06);
+register_visualizer!(2015, 6, visualize);
+
+impl Solution for Day06 {
+    type Input = Vec<Command>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_commands(input)
+    }
+
+    fn part_one(commands: &Self::Input) -> Result<Self::PartOne> {
+        count_lights_on(commands)
+    }
+
+    fn part_two(commands: &Self::Input) -> Result<Self::PartTwo> {
+        Ok(total_brightness(commands)?.0)
+    }
 }
 
 #[cfg(test)]
@@ -212,66 +265,98 @@ mod tests {
 
     #[test]
     fn turn_on_all() {
-        let mut grid = Grid::new();
         let command = Command {
-            start: Point { x: 0, y: 0 },
-            end: Point { x: 999, y: 999 },
+            start: Point::new(0, 0),
+            end: Point::new(999, 999),
             action: Action::TurnOn,
         };
-        grid.apply(command).unwrap();
+        let mut grid = LightGrid::new();
+        grid.apply(&command).unwrap();
 
         assert_eq!(1_000_000, grid.lights_on_count());
     }
 
     #[test]
     fn toggle_first_line() {
-        let mut grid = Grid::new();
         let command = Command {
-            start: Point { x: 0, y: 0 },
-            end: Point { x: 999, y: 0 },
+            start: Point::new(0, 0),
+            end: Point::new(999, 0),
             action: Action::Toggle,
         };
-        grid.apply(command).unwrap();
+        let mut grid = LightGrid::new();
+        grid.apply(&command).unwrap();
 
         assert_eq!(1000, grid.lights_on_count());
     }
 
     #[test]
     fn middle_four_lights_turned_off() {
-        let mut grid = Grid::new_lit();
         let command = Command {
-            start: Point { x: 499, y: 499 },
-            end: Point { x: 500, y: 500 },
+            start: Point::new(499, 499),
+            end: Point::new(500, 500),
             action: Action::TurnOff,
         };
-        grid.apply(command).unwrap();
+        let mut grid = LightGrid::new_lit();
+        grid.apply(&command).unwrap();
 
         assert_eq!(1_000_000 - 4, grid.lights_on_count());
     }
 
     #[test]
     fn increase_brightness_by_one() {
-        let mut grid = Grid::new();
         let command = Command {
-            start: Point { x: 0, y: 0 },
-            end: Point { x: 0, y: 0 },
+            start: Point::new(0, 0),
+            end: Point::new(0, 0),
             action: Action::TurnOn,
         };
-        grid.apply(command).unwrap();
+        let mut grid = BrightnessGrid::new(std::slice::from_ref(&command));
+        grid.apply(&command).unwrap();
 
         assert_eq!(Brightness(1), grid.total_brightness());
     }
 
+    #[test]
+    fn applies_commands_not_known_upfront() {
+        let command = Command {
+            start: Point::new(10, 20),
+            end: Point::new(19, 29),
+            action: Action::Toggle,
+        };
+        let mut grid = BrightnessGrid::new(&[]);
+        grid.apply(&command).unwrap();
+
+        assert_eq!(Brightness(200), grid.total_brightness());
+    }
+
     #[test]
     fn toggling_all_lights_gives_2m_brigtness() {
-        let mut grid = Grid::new();
         let command = Command {
-            start: Point { x: 0, y: 0 },
-            end: Point { x: 999, y: 999 },
+            start: Point::new(0, 0),
+            end: Point::new(999, 999),
             action: Action::Toggle,
         };
-        grid.apply(command).unwrap();
+        let mut grid = BrightnessGrid::new(std::slice::from_ref(&command));
+        grid.apply(&command).unwrap();
 
         assert_eq!(Brightness(2_000_000), grid.total_brightness());
     }
+
+    #[test]
+    fn rejects_lights_outside_of_the_grid() {
+        let command = Command::parse("toggle 0,0 through 1000,0").unwrap();
+        let mut grid = LightGrid::new();
+
+        assert!(grid.apply(&command).is_err());
+    }
+
+    #[test]
+    fn reports_where_a_command_is_invalid() {
+        let input = "turn on 0,0 through 9,9\ntoggle 1,1 through 2,x";
+        let error = parse_commands(input).unwrap_err();
+
+        assert_eq!(
+            "input:2:22: invalid digit found in string: 'x'",
+            error.to_string()
+        );
+    }
 }
@@ -1,178 +1,726 @@
-use common::anyhow::{anyhow, bail, Error};
-use common::{read_input_lines, Day, Result, Year};
+use common::anyhow;
+use common::{register, InputError, Result, Solution};
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Write as _};
+use std::mem;
+use std::num::ParseIntError;
+use std::ops::{BitAnd, BitOr, Not};
 use std::str::FromStr;
 
 const BASE_10: u32 = 10;
 
-type Signal = u16;
+/// The bits carried by a wire, the width of the type being the width of the circuit.
+pub trait Signal:
+    Copy
+    + Eq
+    + Debug
+    + Display
+    + FromStr<Err = ParseIntError>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+{
+    /// Shifting by the width of a signal or more leaves no bits set.
+    fn shift_left(self, amount: Self) -> Self;
+    fn shift_right(self, amount: Self) -> Self;
+}
 
-#[derive(Debug)]
-enum Gate {
-    Value(Signal),
-    Connected(String),
-    Not(String),
-    And(String, String),
-    Or(String, String),
-    LeftShift(String, Signal),
-    RightShift(String, Signal),
+macro_rules! impl_signal {
+    ($($signal:ty),*) => {
+        $(
+            impl Signal for $signal {
+                fn shift_left(self, amount: Self) -> Self {
+                    if amount < Self::BITS as Self {
+                        self << amount
+                    } else {
+                        0
+                    }
+                }
+
+                fn shift_right(self, amount: Self) -> Self {
+                    if amount < Self::BITS as Self {
+                        self >> amount
+                    } else {
+                        0
+                    }
+                }
+            }
+        )*
+    };
 }
 
-impl From<Wire> for Gate {
-    fn from(wire: Wire) -> Self {
-        match wire {
-            Wire::Value(value) => Self::Value(value),
-            Wire::Connected(wire) => Self::Connected(wire),
+impl_signal!(u8, u16, u32, u64);
+
+/// What drives a wire, reading any of its operands from a literal signal or another wire.
+#[derive(Debug, Clone)]
+enum Gate<S> {
+    Connected(Wire<S>),
+    Not(Wire<S>),
+    And(Wire<S>, Wire<S>),
+    Or(Wire<S>, Wire<S>),
+    LeftShift(Wire<S>, Wire<S>),
+    RightShift(Wire<S>, Wire<S>),
+}
+
+impl<S: Signal> Gate<S> {
+    fn operands(&self) -> Vec<&Wire<S>> {
+        match self {
+            Gate::Connected(wire) | Gate::Not(wire) => vec![wire],
+            Gate::And(wire1, wire2)
+            | Gate::Or(wire1, wire2)
+            | Gate::LeftShift(wire1, wire2)
+            | Gate::RightShift(wire1, wire2) => vec![wire1, wire2],
         }
     }
+
+    /// Names of the wires the gate reads.
+    fn inputs(&self) -> Vec<&str> {
+        self.operands().into_iter().filter_map(Wire::name).collect()
+    }
+
+    /// The signal the gate outputs, given the signals of its inputs.
+    fn output(&self, signal: impl Fn(&str) -> S) -> S {
+        let value = |wire: &Wire<S>| match wire {
+            Wire::Value(value) => *value,
+            Wire::Connected(name) => signal(name),
+        };
+
+        match self {
+            Gate::Connected(wire) => value(wire),
+            Gate::Not(wire) => !value(wire),
+            Gate::And(wire1, wire2) => value(wire1) & value(wire2),
+            Gate::Or(wire1, wire2) => value(wire1) | value(wire2),
+            Gate::LeftShift(wire, amount) => value(wire).shift_left(value(amount)),
+            Gate::RightShift(wire, amount) => value(wire).shift_right(value(amount)),
+        }
+    }
+}
+
+/// The gate in instruction syntax, without the wire it drives.
+impl<S: Signal> Display for Gate<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Gate::Connected(wire) => write!(f, "{wire}"),
+            Gate::Not(wire) => write!(f, "NOT {wire}"),
+            Gate::And(wire1, wire2) => write!(f, "{wire1} AND {wire2}"),
+            Gate::Or(wire1, wire2) => write!(f, "{wire1} OR {wire2}"),
+            Gate::LeftShift(wire, amount) => write!(f, "{wire} LSHIFT {amount}"),
+            Gate::RightShift(wire, amount) => write!(f, "{wire} RSHIFT {amount}"),
+        }
+    }
+}
+
+/// The instruction a gate was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub line: usize,
+    pub text: String,
 }
 
-#[derive(Debug)]
-struct Circuit {
-    gates: HashMap<String, Gate>,
+#[derive(Debug, Clone)]
+pub struct Circuit<S = u16> {
+    gates: HashMap<String, Gate<S>>,
+    sources: HashMap<String, Source>,
+    /// Instructions driving a wire that already has a gate, which are ignored.
+    duplicates: Vec<(String, Source)>,
+    /// Signals forced onto wires, replacing whatever drives them.
+    overrides: HashMap<String, S>,
+    /// The gates reading each wire.
+    readers: HashMap<String, Vec<String>>,
+    /// Signals of evaluated wires, dropped once anything they are computed from changes.
+    ///
+    /// A wire is only cached while all of its inputs are, so invalidating can stop at the first
+    /// wire that isn't.
+    cache: RefCell<HashMap<String, S>>,
+    /// Signals dropped from the cache since the changes were last queried.
+    stale: HashMap<String, S>,
 }
 
-impl Circuit {
+impl<S: Signal> Circuit<S> {
     pub fn parse(input: impl IntoIterator<Item = String>) -> Result<Self> {
         let mut gates = HashMap::new();
-        for line in input {
-            let split = line.split(' ').collect_vec();
-
-            match split.as_slice() {
-                [signal, "->", wire] => {
-                    let signal = Wire::from_str(signal)?;
+        let mut sources = HashMap::new();
+        let mut duplicates = Vec::new();
+        for (index, line) in input.into_iter().enumerate() {
+            let (output, gate) =
+                Self::parse_gate(&line).map_err(|error| error.at_line(index + 1, &line))?;
+            let source = Source {
+                line: index + 1,
+                text: line,
+            };
 
-                    gates
-                        .entry(wire.to_string())
-                        .or_insert_with(|| Gate::from(signal));
-                }
-                ["NOT", wire, "->", output] => {
-                    gates
-                        .entry(output.to_string())
-                        .or_insert_with(|| Gate::Not(wire.to_string()));
-                }
-                [wire1, "OR", wire2, "->", output] => {
-                    gates
-                        .entry(output.to_string())
-                        .or_insert_with(|| Gate::Or(wire1.to_string(), wire2.to_string()));
+            match gates.entry(output) {
+                Entry::Occupied(entry) => duplicates.push((entry.key().clone(), source)),
+                Entry::Vacant(entry) => {
+                    sources.insert(entry.key().clone(), source);
+                    entry.insert(gate);
                 }
-                [wire1, "AND", wire2, "->", output] => {
-                    gates
-                        .entry(output.to_string())
-                        .or_insert_with(|| Gate::And(wire1.to_string(), wire2.to_string()));
-                }
-                [wire, "RSHIFT", value, "->", output] => {
-                    let value = Signal::from_str(value)?;
+            }
+        }
 
-                    gates
-                        .entry(output.to_string())
-                        .or_insert_with(|| Gate::RightShift(wire.to_string(), value));
-                }
-                [wire, "LSHIFT", value, "->", output] => {
-                    let value = Signal::from_str(value)?;
+        let mut readers: HashMap<_, Vec<_>> = HashMap::new();
+        for (output, gate) in &gates {
+            for input in gate.inputs() {
+                readers
+                    .entry(input.to_string())
+                    .or_default()
+                    .push(output.clone());
+            }
+        }
 
-                    gates
-                        .entry(output.to_string())
-                        .or_insert_with(|| Gate::LeftShift(wire.to_string(), value));
-                }
+        Ok(Self {
+            gates,
+            sources,
+            duplicates,
+            overrides: HashMap::new(),
+            readers,
+            cache: RefCell::new(HashMap::new()),
+            stale: HashMap::new(),
+        })
+    }
+
+    fn parse_gate(line: &str) -> Result<(String, Gate<S>), InputError> {
+        let wire =
+            |operand: &str| Wire::from_str(operand).map_err(|error| error.within(line, operand));
+
+        let split = line.split(' ').collect_vec();
+        let (output, gate) = match split.as_slice() {
+            [input, "->", output] => (output, Gate::Connected(wire(input)?)),
+            ["NOT", input, "->", output] => (output, Gate::Not(wire(input)?)),
+            [input1, "OR", input2, "->", output] => {
+                (output, Gate::Or(wire(input1)?, wire(input2)?))
+            }
+            [input1, "AND", input2, "->", output] => {
+                (output, Gate::And(wire(input1)?, wire(input2)?))
+            }
+            [input, "RSHIFT", amount, "->", output] => {
+                (output, Gate::RightShift(wire(input)?, wire(amount)?))
+            }
+            [input, "LSHIFT", amount, "->", output] => {
+                (output, Gate::LeftShift(wire(input)?, wire(amount)?))
+            }
+            _ => return Err(InputError::invalid(line, "Unrecognized pattern")),
+        };
+
+        Ok((output.to_string(), gate))
+    }
+
+    /// Forces `signal` onto the wire until it is reset, ignoring the gate driving it.
+    ///
+    /// Unless the wire already carried `signal`, the cached signals of every wire computed from
+    /// it are dropped.
+    pub fn override_wire(&mut self, wire_name: &str, signal: S) {
+        self.overrides.insert(wire_name.to_string(), signal);
+        if self.cache.get_mut().get(wire_name) != Some(&signal) {
+            self.invalidate(wire_name);
+        }
+    }
+
+    /// Lets the wire be driven by its gate again.
+    pub fn reset_wire(&mut self, wire_name: &str) {
+        if self.overrides.remove(wire_name).is_some() {
+            self.invalidate(wire_name);
+        }
+    }
+
+    pub fn reset_all(&mut self) {
+        for wire_name in mem::take(&mut self.overrides).keys() {
+            self.invalidate(wire_name);
+        }
+    }
+
+    /// Wires whose signal differs from the one they had before the overrides or resets since the
+    /// last call, sorted by name.
+    ///
+    /// Only wires that were evaluated before they were invalidated are considered, and a wire
+    /// that no longer exists counts as changed.
+    pub fn changed_wires(&mut self) -> Result<Vec<String>, CircuitError> {
+        let stale = mem::take(&mut self.stale);
+        let defined = stale
+            .keys()
+            .map(String::as_str)
+            .filter(|wire_name| self.check_defined(wire_name, None).is_ok());
+        if let Err(error) = self.evaluate(defined) {
+            self.stale = stale;
+            return Err(error);
+        }
+
+        let cache = self.cache.get_mut();
+        Ok(stale
+            .into_iter()
+            .filter(|(wire_name, signal)| cache.get(wire_name) != Some(signal))
+            .map(|(wire_name, _)| wire_name)
+            .sorted()
+            .collect())
+    }
+
+    /// Drops the cached signals of the wire and every wire computed from it.
+    fn invalidate(&mut self, wire_name: &str) {
+        let cache = self.cache.get_mut();
+        let mut pending = vec![wire_name];
+        while let Some(wire_name) = pending.pop() {
+            if let Some(signal) = cache.remove(wire_name) {
+                self.stale.entry(wire_name.to_string()).or_insert(signal);
+                let readers = self.readers.get(wire_name).into_iter().flatten();
+                pending.extend(readers.map(String::as_str));
+            }
+        }
+    }
+
+    /// Finds everything wrong with the wiring without evaluating it, ordered by line.
+    ///
+    /// Undefined wires and cycles that `outputs` depend on are errors, everything else only a
+    /// warning: duplicate drivers, wires none of `outputs` depend on and problems among them.
+    /// Only the first cycle found among the wires `outputs` depend on is reported, and the first
+    /// one among the rest, since the wires on a cycle may be part of others.
+    pub fn validate(&self, outputs: &[&str]) -> Vec<Diagnostic> {
+        let used = self.dependencies(outputs.iter().copied());
+        let at = |wire_name: &str, problem, used_by_outputs: bool| Diagnostic {
+            problem,
+            source: self.sources.get(wire_name).cloned(),
+            severity: if used_by_outputs {
+                Severity::Error
+            } else {
+                Severity::Warning
+            },
+        };
 
-                _ => {
-                    bail!("Unrecognized pattern: {line}");
+        let mut diagnostics = Vec::new();
+        for (wire_name, source) in &self.duplicates {
+            diagnostics.push(Diagnostic {
+                problem: Problem::DuplicateDriver {
+                    wire: wire_name.clone(),
+                    first_line: self.sources[wire_name].line,
+                },
+                source: Some(source.clone()),
+                severity: Severity::Warning,
+            });
+        }
+        for output in outputs {
+            if let Err(error) = self.check_defined(output, None) {
+                diagnostics.push(at(output, Problem::Invalid(error), true));
+            }
+        }
+        for (wire_name, gate) in &self.gates {
+            let is_used = used.contains(wire_name.as_str());
+            for input in gate.inputs() {
+                if let Err(error) = self.check_defined(input, Some(wire_name)) {
+                    diagnostics.push(at(wire_name, Problem::Invalid(error), is_used));
                 }
             }
+            if !is_used {
+                diagnostics.push(at(wire_name, Problem::Unused(wire_name.clone()), false));
+            }
         }
 
-        Ok(Self { gates })
+        let inputs = |wire_name| self.inputs(wire_name);
+        if let Err(CircuitError::Cycle(cycle)) =
+            self.topological_order(outputs.iter().copied(), true, inputs)
+        {
+            diagnostics.push(at(
+                &cycle[0],
+                Problem::Invalid(CircuitError::Cycle(cycle.clone())),
+                true,
+            ));
+        }
+        let unused = self
+            .gates
+            .keys()
+            .map(String::as_str)
+            .filter(|wire_name| !used.contains(wire_name))
+            .sorted();
+        if let Err(CircuitError::Cycle(cycle)) = self.topological_order(unused, true, inputs) {
+            // A cycle among the used wires was already reported above.
+            if !cycle
+                .iter()
+                .all(|wire_name| used.contains(wire_name.as_str()))
+            {
+                diagnostics.push(at(
+                    &cycle[0],
+                    Problem::Invalid(CircuitError::Cycle(cycle.clone())),
+                    false,
+                ));
+            }
+        }
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.source.as_ref().map(|source| source.line));
+        diagnostics
     }
 
-    pub fn signal_value(&self, wire_name: &str) -> Option<Signal> {
-        let mut cache = HashMap::default();
+    /// The given wires and every wire they are computed from.
+    fn dependencies<'a>(
+        &'a self,
+        wire_names: impl IntoIterator<Item = &'a str>,
+    ) -> HashSet<&'a str> {
+        let mut dependencies = HashSet::new();
+        let mut pending = wire_names.into_iter().collect_vec();
+        while let Some(wire_name) = pending.pop() {
+            if dependencies.insert(wire_name) {
+                pending.extend(self.inputs(wire_name));
+            }
+        }
 
-        self.signal_value_cached(wire_name, &mut cache)
+        dependencies
     }
 
-    fn signal_value_cached(
-        &self,
-        wire_name: &str,
-        cache: &mut HashMap<String, Signal>,
-    ) -> Option<Signal> {
-        let gate = self.gates.get(wire_name)?;
+    /// Evaluates the circuit up to the given wire, reusing the signals cached by earlier calls.
+    pub fn signal_value(&self, wire_name: &str) -> Result<S, CircuitError> {
+        self.evaluate([wire_name])?;
 
-        let result = match gate {
-            Gate::Value(value) => *value,
-            Gate::Connected(connected) => {
-                let result = self.try_fetch_from_cache(connected, cache)?;
-                cache.insert(wire_name.to_string(), result);
+        Ok(self.cache.borrow()[wire_name])
+    }
+
+    /// Evaluates every wire of the circuit, sharing the work between them.
+    pub fn evaluate_all(&self) -> Result<BTreeMap<String, S>, CircuitError> {
+        self.evaluate(
+            self.gates
+                .keys()
+                .chain(self.overrides.keys())
+                .map(String::as_str),
+        )?;
+
+        Ok(self
+            .cache
+            .borrow()
+            .iter()
+            .map(|(wire_name, &signal)| (wire_name.clone(), signal))
+            .collect())
+    }
 
-                result
+    /// Caches the signals of the given wires and everything they depend on, evaluating the
+    /// wires that aren't cached yet in topological order.
+    fn evaluate<'a>(
+        &'a self,
+        wire_names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), CircuitError> {
+        let mut cache = self.cache.borrow_mut();
+        let order = self.topological_order(wire_names, false, |wire_name| {
+            if cache.contains_key(wire_name) {
+                Vec::new()
+            } else {
+                self.inputs(wire_name)
             }
-            Gate::Not(wire) => {
-                let result = self.try_fetch_from_cache(wire, cache)?;
+        })?;
 
-                !result
+        for wire_name in order {
+            if cache.contains_key(wire_name) {
+                continue;
             }
-            Gate::And(wire1, wire2) => {
-                let wire1 = Wire::from_str(wire1).ok().map(|wire| match wire {
-                    Wire::Value(value) => Some(value),
-                    Wire::Connected(connected) => self.try_fetch_from_cache(&connected, cache),
-                })??;
-                let wire2 = self.try_fetch_from_cache(wire2, cache)?;
+            let signal = match self.overrides.get(wire_name) {
+                Some(&signal) => signal,
+                None => self.gates[wire_name].output(|input| cache[input]),
+            };
+            cache.insert(wire_name.to_string(), signal);
+        }
+
+        Ok(())
+    }
 
-                wire1 & wire2
+    /// The circuit as a Graphviz DOT graph, with a node per gate and an edge per wire.
+    ///
+    /// Edges are labeled with their signals if given. Wires nothing reads lead to a node of
+    /// their own, so every wire shows up as an edge.
+    pub fn to_dot(&self, signals: Option<&BTreeMap<String, S>>) -> String {
+        let label = |wire_name: &str| match signals.and_then(|signals| signals.get(wire_name)) {
+            Some(signal) => format!("{wire_name} = {signal}"),
+            None => wire_name.to_string(),
+        };
+        let wire_names = self
+            .gates
+            .keys()
+            .chain(self.overrides.keys())
+            .map(String::as_str)
+            .sorted()
+            .dedup()
+            .collect_vec();
+        let read: HashSet<_> = wire_names
+            .iter()
+            .flat_map(|wire_name| self.inputs(wire_name))
+            .collect();
+
+        let mut dot = String::from("digraph circuit {\n");
+        for &wire_name in &wire_names {
+            let gate = match self.overrides.get(wire_name) {
+                Some(signal) => format!("{signal} (override)"),
+                None => self.gates[wire_name].to_string(),
+            };
+            let _ = writeln!(dot, "    {wire_name:?} [label={gate:?}];");
+        }
+        for &wire_name in &wire_names {
+            for input in self.inputs(wire_name) {
+                let _ = writeln!(
+                    dot,
+                    "    {input:?} -> {wire_name:?} [label={:?}];",
+                    label(input)
+                );
+            }
+            if !read.contains(wire_name) {
+                let output = format!("{wire_name} output");
+                let _ = writeln!(dot, "    {output:?} [shape=plaintext, label=\"\"];");
+                let _ = writeln!(
+                    dot,
+                    "    {wire_name:?} -> {output:?} [label={:?}];",
+                    label(wire_name)
+                );
             }
-            Gate::Or(wire1, wire2) => {
-                let wire1_val = self.try_fetch_from_cache(wire1, cache)?;
-                cache.insert(wire1.to_string(), wire1_val);
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
 
-                let wire2_val = self.try_fetch_from_cache(wire2, cache)?;
-                cache.insert(wire2.to_string(), wire2_val);
+    /// The tree of wires the signal of a wire is computed from, one wire per line with its
+    /// signal and gate, indented below the wire reading it.
+    ///
+    /// A wire read more than once is only expanded the first time.
+    pub fn explain(&self, wire_name: &str) -> Result<String, CircuitError> {
+        self.evaluate([wire_name])?;
+        let signals = self.cache.borrow();
 
-                wire1_val | wire2_val
+        let mut explanation = String::new();
+        let mut explained = HashSet::new();
+        let mut pending = vec![(wire_name, 0)];
+        while let Some((wire_name, depth)) = pending.pop() {
+            let indent = "  ".repeat(depth);
+            let signal = signals[wire_name];
+            if !explained.insert(wire_name) {
+                let _ = writeln!(explanation, "{indent}{wire_name} = {signal} (see above)");
+                continue;
             }
-            Gate::LeftShift(wire, value) => {
-                let signal = self.try_fetch_from_cache(wire, cache)?;
 
-                signal << value
+            match self.overrides.get(wire_name) {
+                Some(_) => {
+                    let _ = writeln!(explanation, "{indent}{wire_name} = {signal} (override)");
+                }
+                None => {
+                    let gate = &self.gates[wire_name];
+                    let _ = writeln!(explanation, "{indent}{wire_name} = {signal} <- {gate}");
+                    let inputs = gate.inputs().into_iter().rev();
+                    pending.extend(inputs.map(|input| (input, depth + 1)));
+                }
             }
-            Gate::RightShift(wire, value) => {
-                let signal = self.try_fetch_from_cache(wire, cache)?;
+        }
+
+        Ok(explanation)
+    }
+
+    /// Orders the given wires and their inputs so that every wire comes after its inputs.
+    ///
+    /// Uses a depth-first search with an explicit stack, so long chains of wires don't overflow.
+    /// With `skip_undefined`, inputs nothing drives are left out instead of reported. The wires
+    /// each wire is computed from are given by `inputs`.
+    fn topological_order<'a>(
+        &'a self,
+        wire_names: impl IntoIterator<Item = &'a str>,
+        skip_undefined: bool,
+        inputs: impl Fn(&'a str) -> Vec<&'a str>,
+    ) -> Result<Vec<&'a str>, CircuitError> {
+        enum Visit {
+            InProgress,
+            Done,
+        }
 
-                signal >> value
+        let mut visits = HashMap::new();
+        let mut order = Vec::new();
+        for root in wire_names {
+            if visits.contains_key(root) {
+                continue;
             }
-        };
+            self.check_defined(root, None)?;
+            visits.insert(root, Visit::InProgress);
+            let mut stack = vec![(root, inputs(root).into_iter())];
+
+            while let Some((wire_name, pending)) = stack.last_mut() {
+                let wire_name = *wire_name;
+                let Some(input) = pending.next() else {
+                    visits.insert(wire_name, Visit::Done);
+                    order.push(wire_name);
+                    stack.pop();
+                    continue;
+                };
 
-        cache.insert(wire_name.to_string(), result);
+                match visits.get(input) {
+                    Some(Visit::Done) => {}
+                    Some(Visit::InProgress) => {
+                        let start = stack
+                            .iter()
+                            .position(|&(wire_name, _)| wire_name == input)
+                            .unwrap_or_default();
+                        let cycle = stack[start..]
+                            .iter()
+                            .map(|(wire_name, _)| wire_name.to_string())
+                            .collect();
 
-        Some(result)
+                        return Err(CircuitError::Cycle(cycle));
+                    }
+                    None => {
+                        match self.check_defined(input, Some(wire_name)) {
+                            Err(_) if skip_undefined => continue,
+                            result => result?,
+                        }
+                        visits.insert(input, Visit::InProgress);
+                        stack.push((input, inputs(input).into_iter()));
+                    }
+                }
+            }
+        }
+
+        Ok(order)
     }
 
-    fn try_fetch_from_cache(
-        &self,
-        wire_name: &str,
-        cache: &mut HashMap<String, Signal>,
-    ) -> Option<Signal> {
-        cache
+    /// Wires the signal of a wire is computed from, none if it is overridden.
+    fn inputs(&self, wire_name: &str) -> Vec<&str> {
+        if self.overrides.contains_key(wire_name) {
+            return Vec::new();
+        }
+
+        self.gates
             .get(wire_name)
-            .cloned()
-            .or_else(|| self.signal_value_cached(wire_name, cache))
+            .map(Gate::inputs)
+            .unwrap_or_default()
+    }
+
+    fn check_defined(&self, wire_name: &str, used_by: Option<&str>) -> Result<(), CircuitError> {
+        if self.gates.contains_key(wire_name) || self.overrides.contains_key(wire_name) {
+            return Ok(());
+        }
+
+        Err(CircuitError::Undefined {
+            wire: wire_name.to_string(),
+            used_by: used_by.map(ToOwned::to_owned),
+        })
+    }
+}
+
+/// Why the signals of a circuit can't be computed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    /// A wire is used, but nothing drives it.
+    Undefined {
+        wire: String,
+        used_by: Option<String>,
+    },
+    /// Wires that depend on themselves, each one on the next and the last one on the first.
+    Cycle(Vec<String>),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undefined {
+                wire,
+                used_by: Some(used_by),
+            } => write!(f, "Wire {wire} is used by {used_by}, but nothing drives it"),
+            Self::Undefined {
+                wire,
+                used_by: None,
+            } => write!(f, "Nothing drives wire {wire}"),
+            Self::Cycle(wires) => {
+                let first = wires.first().map(String::as_str).unwrap_or_default();
+                write!(
+                    f,
+                    "Wires depend on themselves: {} -> {first}",
+                    wires.join(" -> ")
+                )
+            }
+        }
     }
 }
 
+impl Error for CircuitError {}
+
+/// A problem found by [`Circuit::validate`], with the instruction it was found on, if any.
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum Wire {
-    Value(Signal),
+pub struct Diagnostic {
+    pub problem: Problem,
+    pub source: Option<Source>,
+    pub severity: Severity,
+}
+
+/// Whether the outputs a circuit was validated for can still be evaluated despite a problem.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Diagnostic {
+    pub fn is_warning(&self) -> bool {
+        self.severity == Severity::Warning
+    }
+
+    /// An error pointing at the line of the instruction, if there is one.
+    pub fn into_error(self) -> anyhow::Error {
+        match self.source {
+            Some(Source { line, text }) => InputError::invalid(&text, self.problem)
+                .at_line(line, &text)
+                .into(),
+            None => self.problem.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// A wire has another driver on an earlier line, which is the one used.
+    DuplicateDriver { wire: String, first_line: usize },
+    /// A wire the outputs don't depend on.
+    Unused(String),
+    /// Something that makes evaluating the circuit fail.
+    Invalid(CircuitError),
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateDriver { wire, first_line } => {
+                write!(f, "Wire {wire} is already driven on line {first_line}")
+            }
+            Self::Unused(wire) => write!(f, "Wire {wire} is never used"),
+            Self::Invalid(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for Problem {}
+
+/// An operand of a gate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Wire<S> {
+    Value(S),
     Connected(String),
 }
 
-impl FromStr for Wire {
-    type Err = Error;
+impl<S> Wire<S> {
+    fn name(&self) -> Option<&str> {
+        match self {
+            Wire::Value(_) => None,
+            Wire::Connected(name) => Some(name),
+        }
+    }
+}
+
+impl<S: Signal> Display for Wire<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Wire::Value(value) => write!(f, "{value}"),
+            Wire::Connected(name) => f.write_str(name),
+        }
+    }
+}
+
+impl<S: Signal> FromStr for Wire<S> {
+    type Err = InputError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let is_numeric = s.chars().all(|c| c.is_digit(BASE_10));
 
         let wire = if is_numeric {
-            Self::Value(s.parse()?)
+            Self::Value(s.parse().map_err(|error| InputError::invalid(s, error))?)
         } else {
             Self::Connected(s.to_string())
         };
@@ -181,23 +729,377 @@ impl FromStr for Wire {
     }
 }
 
-fn main() -> Result<()> {
-    let input = read_input_lines(Year(2015), Day(7))?;
-    let input: Vec<_> = input.into_iter().try_collect()?;
-    let circuit = Circuit::parse(input)?;
-    let a = circuit
-        .signal_value("a")
-        .ok_or_else(|| anyhow!("Failed to fetch value of a"))?;
+pub struct Day07;
+
+register!(2015, 7, Day07);
 
-    println!("Value of signal on wire 'a' is {a}");
+impl Solution for Day07 {
+    type Input = Circuit;
+    type PartOne = u16;
+    type PartTwo = u16;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input> {
+        let circuit = Circuit::parse(input.lines().map(ToOwned::to_owned))?;
+        let validation = circuit.validate(&["a"]);
+        if let Some(diagnostic) = validation
+            .into_iter()
+            .find(|diagnostic| !diagnostic.is_warning())
+        {
+            return Err(diagnostic.into_error());
+        }
+
+        Ok(circuit)
+    }
+
+    fn part_one(circuit: &Self::Input) -> Result<Self::PartOne> {
+        Ok(circuit.signal_value("a")?)
+    }
+
+    fn part_two(circuit: &Self::Input) -> Result<Self::PartTwo> {
+        let a = Self::part_one(circuit)?;
+        let mut circuit = circuit.clone();
+        circuit.override_wire("b", a);
+
+        Ok(circuit.signal_value("a")?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &[&str] = &[
+        "123 -> x",
+        "456 -> y",
+        "x AND y -> d",
+        "x OR y -> e",
+        "x LSHIFT 2 -> f",
+        "y RSHIFT 2 -> g",
+        "NOT x -> h",
+        "NOT y -> i",
+    ];
+
+    fn example() -> Circuit {
+        Circuit::<u16>::parse(EXAMPLE.iter().map(|&line| line.to_owned())).unwrap()
+    }
+
+    #[test]
+    fn overrides_and_resets_wires() {
+        let mut circuit = example();
+        circuit.override_wire("x", 1);
+        circuit.override_wire("d", 7);
+
+        assert_eq!(Ok(457), circuit.signal_value("e"));
+        assert_eq!(Ok(7), circuit.signal_value("d"));
+
+        circuit.reset_wire("d");
+        assert_eq!(Ok(0), circuit.signal_value("d"));
+
+        circuit.reset_all();
+        assert_eq!(Ok(72), circuit.signal_value("d"));
+
+        circuit.override_wire("unconnected", 5);
+        assert_eq!(Ok(5), circuit.signal_value("unconnected"));
+    }
+
+    #[test]
+    fn reevaluates_only_wires_downstream_of_an_override() {
+        let mut circuit = example();
+        let before = circuit.evaluate_all().unwrap();
+
+        circuit.override_wire("x", 123);
+        assert_eq!(Ok(Vec::<String>::new()), circuit.changed_wires());
+
+        circuit.override_wire("y", 0);
+        {
+            let cache = circuit.cache.borrow();
+            assert!(["x", "f", "h"].iter().all(|&wire| cache.contains_key(wire)));
+            assert!(["y", "d", "e", "g", "i"]
+                .iter()
+                .all(|&wire| !cache.contains_key(wire)));
+        }
+        assert_eq!(
+            Ok(vec![
+                "d".into(),
+                "e".into(),
+                "g".into(),
+                "i".into(),
+                "y".into()
+            ]),
+            circuit.changed_wires()
+        );
+        assert_eq!(Ok(123), circuit.signal_value("e"));
+
+        circuit.reset_all();
+        assert_eq!(Ok(before), circuit.evaluate_all());
+        assert_eq!(
+            Ok(vec![
+                "d".into(),
+                "e".into(),
+                "g".into(),
+                "i".into(),
+                "y".into()
+            ]),
+            circuit.changed_wires()
+        );
+    }
+
+    #[test]
+    fn evaluates_all_wires() {
+        let signals = example().evaluate_all();
+
+        let signals = signals.unwrap();
+        assert_eq!(8, signals.len());
+        assert_eq!(Some(&65079), signals.get("i"));
+    }
+
+    #[test]
+    fn evaluates_long_chains_without_recursion() {
+        let chain = (1..100_000).map(|n| format!("w{} -> w{n}", n - 1));
+        let circuit =
+            Circuit::<u16>::parse(std::iter::once("7 -> w0".to_owned()).chain(chain)).unwrap();
+
+        assert_eq!(Ok(7), circuit.signal_value("w99999"));
+    }
+
+    #[test]
+    fn reports_cycles_and_undefined_wires() {
+        let circuit = Circuit::<u16>::parse(
+            ["c -> a", "a OR x -> b", "NOT b -> c", "y -> d"]
+                .into_iter()
+                .map(ToOwned::to_owned),
+        )
+        .unwrap();
+
+        let error = circuit.signal_value("a").unwrap_err();
+        assert_eq!(
+            CircuitError::Cycle(vec!["a".into(), "c".into(), "b".into()]),
+            error
+        );
+        assert_eq!(
+            "Wires depend on themselves: a -> c -> b -> a",
+            error.to_string()
+        );
+
+        assert_eq!(
+            Err(CircuitError::Undefined {
+                wire: "y".into(),
+                used_by: Some("d".into())
+            }),
+            circuit.signal_value("d")
+        );
+    }
+
+    #[test]
+    fn validates_the_wiring_with_source_lines() {
+        let circuit = Circuit::<u16>::parse(
+            [
+                "1 -> x",
+                "x AND y -> a",
+                "2 -> x",
+                "NOT z -> b",
+                "c -> z",
+                "z -> c",
+            ]
+            .into_iter()
+            .map(ToOwned::to_owned),
+        )
+        .unwrap();
+
+        let diagnostics = circuit.validate(&["a"]);
+        let lines = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let line = diagnostic.source.as_ref().map(|source| source.line);
+                (line, diagnostic.severity, diagnostic.problem.to_string())
+            })
+            .collect_vec();
+        let (error, warning) = (Severity::Error, Severity::Warning);
+        assert_eq!(
+            vec![
+                (
+                    Some(2),
+                    error,
+                    "Wire y is used by a, but nothing drives it".to_owned()
+                ),
+                (
+                    Some(3),
+                    warning,
+                    "Wire x is already driven on line 1".to_owned()
+                ),
+                (Some(4), warning, "Wire b is never used".to_owned()),
+                (Some(5), warning, "Wire z is never used".to_owned()),
+                (
+                    Some(5),
+                    warning,
+                    "Wires depend on themselves: z -> c -> z".to_owned()
+                ),
+                (Some(6), warning, "Wire c is never used".to_owned()),
+            ],
+            lines
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_wiring_before_evaluating() {
+        let error = Day07::parse("1 -> x\nx AND y -> a\n").unwrap_err();
+        assert_eq!(
+            "input:2:1: Wire y is used by a, but nothing drives it: 'x AND y -> a'",
+            error.to_string()
+        );
+
+        let error = Day07::parse("b -> a\nNOT a -> b\n").unwrap_err();
+        assert_eq!(
+            "input:1:1: Wires depend on themselves: a -> b -> a: 'b -> a'",
+            error.to_string()
+        );
+
+        assert_eq!(
+            "Nothing drives wire a",
+            Day07::parse("1 -> b\n").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn evaluates_despite_broken_wiring_the_output_does_not_depend_on() {
+        for input in [
+            "1 -> a\nq -> unused\n",
+            "1 -> a\nx -> y\ny -> x\n",
+            "1 -> a\n2 -> a\n",
+        ] {
+            let circuit = Day07::parse(input).unwrap();
+
+            assert_eq!(1, Day07::part_one(&circuit).unwrap(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn exports_the_wiring_as_dot() {
+        let mut circuit = example();
+        circuit.override_wire("y", 1);
+        let signals = circuit.evaluate_all().unwrap();
+
+        let dot = circuit.to_dot(Some(&signals));
+        assert!(dot.starts_with("digraph circuit {\n"));
+        assert!(dot.contains("    \"d\" [label=\"x AND y\"];\n"));
+        assert!(dot.contains("    \"y\" [label=\"1 (override)\"];\n"));
+        assert!(dot.contains("    \"x\" -> \"d\" [label=\"x = 123\"];\n"));
+        assert!(dot.contains("    \"d\" -> \"d output\" [label=\"d = 1\"];\n"));
+        assert!(!dot.contains("\"x output\""));
+
+        let dot = circuit.to_dot(None);
+        assert!(dot.contains("    \"y\" -> \"e\" [label=\"y\"];\n"));
+    }
+
+    #[test]
+    fn explains_how_a_signal_is_computed() {
+        let mut circuit = Circuit::<u16>::parse(
+            ["3 -> x", "x AND y -> z", "z OR x -> a", "NOT 0 -> y"]
+                .into_iter()
+                .map(ToOwned::to_owned),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "a = 3 <- z OR x\n\
+             \x20 z = 3 <- x AND y\n\
+             \x20   x = 3 <- 3\n\
+             \x20   y = 65535 <- NOT 0\n\
+             \x20 x = 3 (see above)\n",
+            circuit.explain("a").unwrap()
+        );
+
+        circuit.override_wire("z", 4);
+        assert_eq!(
+            "a = 7 <- z OR x\n  z = 4 (override)\n  x = 3 <- 3\n",
+            circuit.explain("a").unwrap()
+        );
+        assert!(circuit.explain("b").is_err());
+    }
+
+    /// Every gate output, computed from two literal operands.
+    fn outputs<S: Signal>(a: S, b: S) -> [S; 6] {
+        let (a, b) = (Wire::Value(a), Wire::Value(b));
+
+        [
+            Gate::Connected(a.clone()),
+            Gate::Not(a.clone()),
+            Gate::And(a.clone(), b.clone()),
+            Gate::Or(a.clone(), b.clone()),
+            Gate::LeftShift(a.clone(), b.clone()),
+            Gate::RightShift(a, b),
+        ]
+        .map(|gate| gate.output(|_| unreachable!("No gate reads a wire")))
+    }
+
+    #[test]
+    fn narrow_signals_are_wide_ones_truncated() {
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                let wide = outputs(u64::from(a), u64::from(b)).map(|signal| signal as u8);
+                assert_eq!(wide, outputs(a, b), "operands {a} and {b}");
+            }
+        }
+
+        for a in (0..=u16::MAX).step_by(251) {
+            for b in 0..=32 {
+                let wide = outputs(u32::from(a), u32::from(b)).map(|signal| signal as u16);
+                assert_eq!(wide, outputs(a, b), "operands {a} and {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn evaluates_circuits_of_any_width() {
+        let lines = [
+            "200 -> x",
+            "NOT x -> a",
+            "x LSHIFT 1 -> b",
+            "x RSHIFT 7 -> c",
+        ];
+        let lines = || lines.into_iter().map(ToOwned::to_owned);
+
+        let narrow = Circuit::<u8>::parse(lines()).unwrap();
+        assert_eq!(Ok(55), narrow.signal_value("a"));
+        assert_eq!(Ok(144), narrow.signal_value("b"));
+        assert_eq!(Ok(1), narrow.signal_value("c"));
+
+        let wide = Circuit::<u64>::parse(lines()).unwrap();
+        assert_eq!(Ok(u64::MAX - 200), wide.signal_value("a"));
+        assert_eq!(Ok(400), wide.signal_value("b"));
+
+        let error = Circuit::<u8>::parse(["256 -> x".to_owned()]).unwrap_err();
+        assert_eq!(
+            "input:1:1: number too large to fit in target type: '256'",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn accepts_literals_and_wires_as_any_operand() {
+        let circuit = Circuit::<u16>::parse(
+            [
+                "3 -> x",
+                "1 OR x -> a",
+                "NOT 5 -> b",
+                "12 AND x -> c",
+                "1 LSHIFT x -> d",
+                "x RSHIFT 1 -> e",
+                "x LSHIFT 16 -> f",
+            ]
+            .into_iter()
+            .map(ToOwned::to_owned),
+        )
+        .unwrap();
+
+        assert_eq!(Ok(3), circuit.signal_value("a"));
+        assert_eq!(Ok(65530), circuit.signal_value("b"));
+        assert_eq!(Ok(0), circuit.signal_value("c"));
+        assert_eq!(Ok(8), circuit.signal_value("d"));
+        assert_eq!(Ok(1), circuit.signal_value("e"));
+        assert_eq!(Ok(0), circuit.signal_value("f"));
+    }
+
     #[test]
     fn example_from_task_description() {
         let input = vec![
@@ -213,15 +1115,15 @@ mod tests {
         .into_iter()
         .map(ToOwned::to_owned);
 
-        let circuit = Circuit::parse(input).unwrap();
+        let circuit = Circuit::<u16>::parse(input).unwrap();
 
-        assert_eq!(Some(72), circuit.signal_value("d"));
-        assert_eq!(Some(507), circuit.signal_value("e"));
-        assert_eq!(Some(492), circuit.signal_value("f"));
-        assert_eq!(Some(114), circuit.signal_value("g"));
-        assert_eq!(Some(65412), circuit.signal_value("h"));
-        assert_eq!(Some(65079), circuit.signal_value("i"));
-        assert_eq!(Some(123), circuit.signal_value("x"));
-        assert_eq!(Some(456), circuit.signal_value("y"));
+        assert_eq!(Ok(72), circuit.signal_value("d"));
+        assert_eq!(Ok(507), circuit.signal_value("e"));
+        assert_eq!(Ok(492), circuit.signal_value("f"));
+        assert_eq!(Ok(114), circuit.signal_value("g"));
+        assert_eq!(Ok(65412), circuit.signal_value("h"));
+        assert_eq!(Ok(65079), circuit.signal_value("i"));
+        assert_eq!(Ok(123), circuit.signal_value("x"));
+        assert_eq!(Ok(456), circuit.signal_value("y"));
     }
 }
@@ -0,0 +1,33 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+
+#[cfg(test)]
+mod tests {
+    use common::{registrations, verify, Year};
+
+    #[test]
+    fn days_are_registered_once() {
+        let mut days: Vec<_> = registrations()
+            .filter(|registration| registration.year == Year(2015))
+            .map(|registration| registration.day)
+            .collect();
+        let registered = days.len();
+        days.sort_unstable();
+        days.dedup();
+
+        assert!(registered > 0);
+        assert_eq!(registered, days.len());
+    }
+
+    #[test]
+    fn known_answers_match() {
+        let report = verify(Year(2015)).unwrap();
+
+        assert!(report.is_success(), "{report}");
+    }
+}